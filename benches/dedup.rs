@@ -0,0 +1,74 @@
+//! Compares the size of schemas generated with different inlining and
+//! deduplication settings, and checks that deduplication actually shrinks the
+//! inlined schema. Run with `cargo bench --bench dedup`.
+
+use std::time::Instant;
+
+use jtd_derive::{Generator, JsonTypedef};
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Rect {
+    top_left: Point,
+    bottom_right: Point,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Scene {
+    origin: Point,
+    bounds: Rect,
+    viewport: Option<Rect>,
+    points: Vec<Point>,
+    rects: Vec<Rect>,
+}
+
+/// Prints the stats of the schema of `Scene` and returns its node count.
+fn measure(label: &str, gen: impl Fn() -> Generator) -> usize {
+    const ITERATIONS: u32 = 1000;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        gen().into_root_schema::<Scene>().unwrap();
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+
+    let root = gen().into_root_schema::<Scene>().unwrap();
+    println!(
+        "{:<14} {:>5} nodes {:>6} bytes {:>3} definitions {:>10?}/iter",
+        label,
+        root.count_nodes(),
+        root.serialized_size(),
+        root.definitions.len(),
+        elapsed,
+    );
+
+    root.count_nodes()
+}
+
+fn main() {
+    measure("default", Generator::default);
+    let inlined = measure("inline", || Generator::builder().prefer_inline().build());
+    for threshold in [1, 2, 4] {
+        let deduped = measure(&format!("dedup({})", threshold), || {
+            Generator::builder()
+                .prefer_inline()
+                .dedup_anonymous(threshold)
+                .build()
+        });
+        assert!(
+            deduped < inlined,
+            "dedup({}) should have fewer nodes than inline: {} >= {}",
+            threshold,
+            deduped,
+            inlined
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Schemas for a small REST API, bundled into a single document with shared
+//! definitions. Run with `cargo run --example api`.
+
+use std::collections::HashMap;
+
+use jtd_derive::{register_schema, Generator, JsonTypedef, SchemaRegistry};
+use serde::{Deserialize, Serialize};
+
+/// A user account.
+#[derive(JsonTypedef, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: u32,
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[typedef(optional)]
+    pub email: Option<String>,
+    pub address: Address,
+    pub settings: HashMap<String, String>,
+}
+
+#[derive(JsonTypedef, Serialize, Deserialize)]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+    pub country_code: String,
+}
+
+#[derive(JsonTypedef, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateUserRequest {
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[typedef(optional)]
+    pub email: Option<String>,
+    pub address: Address,
+}
+
+/// Something that happened to a user.
+#[derive(JsonTypedef, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Event {
+    UserCreated { user: User },
+    UserRenamed { id: u32, display_name: String },
+    UserDeleted { id: u32 },
+}
+
+#[derive(JsonTypedef, Serialize, Deserialize)]
+pub struct EventPage {
+    pub events: Vec<Event>,
+    pub next_cursor: Option<String>,
+}
+
+fn main() {
+    // Only the request/response bodies are registered. Everything they refer to
+    // ends up in the shared definitions under its short name.
+    let mut registry = SchemaRegistry::new();
+    register_schema!(registry, CreateUserRequest, EventPage);
+
+    let bundle = registry
+        .into_bundle(Generator::builder().naming_short().build())
+        .expect("no name collisions");
+
+    println!("{}", bundle.to_pretty_canonical_string());
+}
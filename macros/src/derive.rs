@@ -7,20 +7,67 @@ use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use serde_derive_internals::attr::RenameRule;
 use syn::{
-    parse_quote, DataEnum, DataStruct, DeriveInput, Fields, FieldsNamed, GenericParam, Generics,
-    Ident, ItemImpl,
+    parse_quote, punctuated::Punctuated, DataEnum, DataStruct, DeriveInput, Expr, ExprGroup,
+    ExprLit, ExprParen, ExprUnary, Fields, FieldsNamed, GenericParam, Generics, Ident, ItemImpl,
+    Lit, LitStr, UnOp,
 };
 
 use crate::{derive::field::Field, iter_ext::IterExt};
 
-use self::context::Container;
+use self::context::{Container, VariantCtx};
+
+/// Enums with only unit variants that are serialized as plain strings can be
+/// map keys, as can anything marked with `map_key`. Anything invalid is
+/// reported by [`derive`], so this just gives up.
+pub fn derive_map_key(input: &DeriveInput) -> Option<ItemImpl> {
+    let ctx = Container::from_input(input).ok()?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let map_key_impl = parse_quote! {
+        impl #impl_generics ::jtd_derive::JsonTypedefMapKey for #ident #ty_generics #where_clause {}
+    };
+
+    if ctx.map_key {
+        return Some(map_key_impl);
+    }
+
+    let syn::Data::Enum(enu) = &input.data else {
+        return None;
+    };
+
+    if ctx.tag_type != context::TagType::External
+        || ctx.repr.is_some()
+        || ctx.type_from.is_some()
+        || ctx.type_try_from.is_some()
+    {
+        return None;
+    }
+
+    for variant in &enu.variants {
+        if !matches!(variant.fields, Fields::Unit)
+            || VariantCtx::from_input(variant).ok()?.null_variant
+        {
+            return None;
+        }
+    }
+
+    Some(map_key_impl)
+}
 
 pub fn derive(input: DeriveInput) -> Result<ItemImpl, syn::Error> {
     let ctx = context::Container::from_input(&input)?;
 
     let ident = input.ident;
 
-    let (impl_generics_no_infer, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    if let Some(bound) = &ctx.bound {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+    }
+
+    let (impl_generics_no_infer, ty_generics, where_clause) = generics.split_for_impl();
     let mut impl_generics: Generics = parse_quote! {#impl_generics_no_infer};
     for param in impl_generics.params.iter_mut() {
         if let GenericParam::Type(ty) = param {
@@ -94,11 +141,21 @@ pub fn derive(input: DeriveInput) -> Result<ItemImpl, syn::Error> {
             quote_spanned! {ident.span()=> compile_error!("jtd-derive does not support unions")}
         }
     };
+    let doc = gen_codegen_description(&ctx.doc);
     let meta = gen_metadata(&ctx.metadata);
+    let meta_from = ctx.metadata_from.iter();
+    let validate_with = ctx.validate_with.iter();
 
     let res = quote! { {
         let mut schema = #res;
+        #doc
         schema.metadata.extend(#meta);
+        #(schema.metadata.extend(#meta_from());)*
+        #(
+            if let ::std::result::Result::Err(message) = #validate_with(&schema) {
+                gen.reject::<Self>(message);
+            }
+        )*
         schema
     } };
 
@@ -124,17 +181,61 @@ fn gen_struct_schema(
     ident: &Ident,
     s: DataStruct,
 ) -> Result<TokenStream, syn::Error> {
+    if ctx.enum_values.is_some() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(enum(...))] is only allowed on enums with unit variants",
+        ));
+    }
+
+    if ctx.single_variant_as_properties {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(single_variant_as_properties)] is only allowed on enums",
+        ));
+    }
+
+    if ctx.repr.is_some() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(repr = \"...\")] is only allowed on enums",
+        ));
+    }
+
+    if let Some(item) = &ctx.transparent_elements {
+        return match s.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(parse_quote! {
+                Schema {
+                    ty: SchemaType::Elements {
+                        elements: ::std::boxed::Box::new(gen.sub_schema::<#item>()),
+                    },
+                    ..::jtd_derive::schema::Schema::default()
+                }
+            }),
+            _ => Err(syn::Error::new_spanned(
+                ident,
+                "#[typedef(transparent_elements = \"...\")] requires a tuple struct with exactly one field",
+            )),
+        };
+    }
+
+    if ctx.as_values {
+        return match &s.fields {
+            Fields::Named(fields) => gen_values_schema(ident, fields),
+            _ => Err(syn::Error::new_spanned(
+                ident,
+                "#[typedef(as_values)] requires a struct with named fields",
+            )),
+        };
+    }
+
     match s.fields {
         Fields::Named(_) if s.fields.is_empty() => Err(syn::Error::new_spanned(
             ident,
             "jtd-derive does not support empty cstruct-like structs",
         )),
         Fields::Named(fields) if s.fields.len() == 1 && ctx.transparent => {
-            let ty = &fields.named[0].ty;
-
-            Ok(parse_quote! {
-                gen.sub_schema::<#ty>()
-            })
+            Ok(Field::from_syn_field(&fields.named[0])?.schema())
         }
         Fields::Named(fields) => {
             if ctx.transparent {
@@ -148,11 +249,7 @@ fn gen_struct_schema(
             }
         }
         Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
-            let ty = &fields.unnamed[0].ty;
-
-            Ok(parse_quote! {
-                gen.sub_schema::<#ty>()
-            })
+            Ok(Field::from_syn_field(&fields.unnamed[0])?.schema())
         }
         Fields::Unnamed(_) => Err(syn::Error::new_spanned(
             ident,
@@ -168,7 +265,7 @@ fn gen_struct_schema(
 fn gen_enum_schema(
     ctx: &Container,
     ident: &Ident,
-    enu: DataEnum,
+    mut enu: DataEnum,
 ) -> Result<TokenStream, syn::Error> {
     if ctx.transparent {
         return Err(syn::Error::new_spanned(
@@ -184,66 +281,305 @@ fn gen_enum_schema(
         ));
     }
 
-    match enum_kind(ident, &enu)? {
+    if ctx.transparent_elements.is_some() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(transparent_elements = \"...\")] is not allowed on an enum",
+        ));
+    }
+
+    if ctx.as_values {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(as_values)] is not allowed on an enum",
+        ));
+    }
+
+    let variant_ctxs: Vec<_> = enu
+        .variants
+        .iter()
+        .map(VariantCtx::from_input)
+        .collect_fallible()?;
+
+    if matches!(
+        ctx.tag_type,
+        context::TagType::External | context::TagType::Untagged
+    ) {
+        if let Some((v, _)) = enu
+            .variants
+            .iter()
+            .zip(&variant_ctxs)
+            .find(|(_, v_ctx)| v_ctx.tag_value.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                v,
+                "#[typedef(tag_value = \"...\")] requires the enum to have a tag",
+            ));
+        }
+    }
+
+    let mut null_variants = enu
+        .variants
+        .iter()
+        .zip(&variant_ctxs)
+        .filter(|(_, v_ctx)| v_ctx.null_variant)
+        .map(|(v, _)| v);
+    let null_variant = null_variants.next();
+    if let Some(other) = null_variants.next() {
+        return Err(syn::Error::new_spanned(
+            other,
+            "only one variant can be #[typedef(null_variant)]",
+        ));
+    }
+    if let Some(v) = null_variant {
+        if !matches!(v.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                v,
+                "#[typedef(null_variant)] requires a unit variant",
+            ));
+        }
+        if enu.variants.len() == 1 {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "#[typedef(null_variant)] requires the enum to have other variants",
+            ));
+        }
+    }
+    let nullable = null_variant.is_some();
+
+    // the null variant is represented by `nullable`, so the rest of the schema
+    // is generated as if it didn't exist
+    let (variants, variant_ctxs): (Punctuated<_, _>, Vec<_>) = enu
+        .variants
+        .into_iter()
+        .zip(variant_ctxs)
+        .filter(|(_, v_ctx)| !v_ctx.null_variant)
+        .unzip();
+    enu.variants = variants;
+
+    if ctx.single_variant_as_properties {
+        if enu.variants.len() != 1 {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "#[typedef(single_variant_as_properties)] requires the enum to have exactly one variant",
+            ));
+        }
+
+        if !matches!(
+            ctx.tag_type,
+            context::TagType::Internal(_) | context::TagType::Adjacent { .. }
+        ) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "#[typedef(single_variant_as_properties)] requires the enum to have a tag",
+            ));
+        }
+    }
+
+    let schema = gen_enum_variants_schema(ctx, ident, &enu, &variant_ctxs)?;
+
+    if nullable {
+        Ok(quote! { {
+            let mut schema = #schema;
+            schema.nullable = true;
+            schema
+        } })
+    } else {
+        Ok(schema)
+    }
+}
+
+fn gen_enum_variants_schema(
+    ctx: &Container,
+    ident: &Ident,
+    enu: &DataEnum,
+    variant_ctxs: &[VariantCtx],
+) -> Result<TokenStream, syn::Error> {
+    if let Some(repr) = &ctx.repr {
+        return gen_repr_enum_schema(ctx, ident, enu, repr);
+    }
+
+    if ctx.tag_type == context::TagType::Untagged {
+        return gen_untagged_enum_schema(ctx, ident, enu);
+    }
+
+    let idents: Vec<_> = enu
+        .variants
+        .iter()
+        .zip(variant_ctxs)
+        .map(
+            |(v, v_ctx)| match v_ctx.tag_value.as_ref().or(v_ctx.rename.as_ref()) {
+                Some(name) => name.clone(),
+                None => match ctx.rename_rule {
+                    Some(rule) => rule.apply_to_variant(&v.ident.to_string()),
+                    None => v.ident.to_string(),
+                },
+            },
+        )
+        .collect();
+
+    if ctx.enum_values.is_none() {
+        for (i, (variant, value)) in enu.variants.iter().zip(&idents).enumerate() {
+            if let Some(other) = idents[..i]
+                .iter()
+                .position(|other| other == value)
+                .map(|j| &enu.variants[j])
+            {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    format!(
+                        "variants `{}` and `{}` are both represented as `{}`",
+                        other.ident, variant.ident, value
+                    ),
+                ));
+            }
+        }
+    }
+
+    match enum_kind(ident, enu)? {
         EnumKind::UnitVariants => {
-            let mut idents: Vec<_> = enu.variants.iter().map(|v| v.ident.to_string()).collect();
-            if let Some(rule) = ctx.rename_rule {
-                for ident in idents.iter_mut() {
-                    *ident = rule.apply_to_variant(ident);
+            let values = match &ctx.enum_values {
+                Some(values) => values.iter().map(|v| v.value()).collect(),
+                None => idents.clone(),
+            };
+
+            // the descriptions are keyed by how serde names each variant, so
+            // variants missing from an overridden list of values are left out
+            let (described, descriptions): (Vec<_>, Vec<_>) = idents
+                .iter()
+                .zip(variant_ctxs)
+                .filter(|(name, _)| values.contains(name))
+                .filter_map(|(name, v_ctx)| Some((name, v_ctx.doc.as_ref()?)))
+                .unzip();
+
+            let enum_description = (!described.is_empty()).then(|| {
+                quote! {
+                    if gen.dialect() == ::jtd_derive::Dialect::JtdCodegen {
+                        schema.metadata.insert(
+                            "enumDescription",
+                            [#((#described, #descriptions)),*].into_iter().collect(),
+                        );
+                    }
                 }
-            }
+            });
 
-            let enum_schema = parse_quote! {
-                Schema {
+            let enum_schema = quote! { {
+                let mut schema = Schema {
                     ty: SchemaType::Enum {
-                        r#enum: [#(#idents),*].into(),
+                        r#enum: [#(#values),*].into(),
                     },
                     ..::jtd_derive::schema::Schema::default()
-                }
-            };
+                };
+                #enum_description
+                schema
+            } };
 
             match &ctx.tag_type {
                 context::TagType::External => Ok(enum_schema),
-                context::TagType::Internal(tag) => Ok(parse_quote! {
-                    Schema {
-                        ty: SchemaType::Properties {
-                            properties: [
-                                (#tag, #enum_schema)
-                            ].into(),
-                            additional_properties: true,
-                            optional_properties: [].into(),
-                        },
-                        ..::jtd_derive::schema::Schema::default()
-                    }
-                }),
+                // serde omits the content of unit variants, so both of these
+                // end up as an object with just the tag
+                context::TagType::Internal(tag) | context::TagType::Adjacent { tag, .. } => {
+                    Ok(parse_quote! {
+                        Schema {
+                            ty: SchemaType::Properties {
+                                properties: [
+                                    (#tag, #enum_schema)
+                                ].into(),
+                                additional_properties: true,
+                                optional_properties: [].into(),
+                            },
+                            ..::jtd_derive::schema::Schema::default()
+                        }
+                    })
+                }
+                context::TagType::Untagged => unreachable!("untagged enums are handled separately"),
             }
         }
         EnumKind::StructVariants => {
-            let tag = match &ctx.tag_type {
+            if ctx.enum_values.is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "#[typedef(enum(...))] is only allowed on enums with unit variants",
+                ));
+            }
+
+            let (tag, content) = match &ctx.tag_type {
                 context::TagType::External => {
                     return Err(syn::Error::new_spanned(
                         ident,
-                        "jtd-derive requires an enum with struct variants to have a tag",
+                        "jtd-derive requires an enum with struct variants to have a tag; \
+                         the externally tagged representation serde uses by default, \
+                         e.g. `{\"Variant\": {...}}`, can't be expressed in Typedef. \
+                         Consider `#[serde(tag = \"type\")]`, or `#[typedef(tag = \"type\")]` \
+                         if the type isn't serialized with serde",
                     ));
                 }
-                context::TagType::Internal(t) => t,
+                context::TagType::Internal(t) => (t, None),
+                context::TagType::Adjacent { tag, content } => (tag, Some(content)),
+                context::TagType::Untagged => unreachable!("untagged enums are handled separately"),
             };
 
-            let (mut idents, variants): (Vec<_>, Vec<_>) = enu
+            let variants: Vec<_> = enu
                 .variants
                 .iter()
-                .map(|v| {
-                    (
-                        v.ident.to_string(),
-                        gen_named_fields(ctx, unwrap_fields_named(&v.fields), None),
-                    )
+                .zip(variant_ctxs)
+                .map(|(v, v_ctx)| {
+                    gen_named_fields(ctx, unwrap_fields_named(&v.fields), v_ctx.rename_rule)
                 })
-                .unzip();
-            let variants: Vec<_> = variants.into_iter().collect_fallible()?;
-            if let Some(rule) = ctx.rename_rule {
-                for ident in idents.iter_mut() {
-                    *ident = rule.apply_to_variant(ident);
+                .collect_fallible()?;
+
+            let variants: Vec<TokenStream> = match content {
+                None => variants,
+                Some(content) => {
+                    let additional = gen_additional_properties(ctx);
+
+                    variants
+                        .into_iter()
+                        .map(|variant| {
+                            parse_quote! {
+                                Schema {
+                                    ty: SchemaType::Properties {
+                                        properties: [(#content, #variant)].into(),
+                                        optional_properties: [].into(),
+                                        additional_properties: #additional,
+                                    },
+                                    ..::jtd_derive::schema::Schema::default()
+                                }
+                            }
+                        })
+                        .collect()
                 }
+            };
+
+            let variants: Vec<TokenStream> = variants
+                .into_iter()
+                .zip(variant_ctxs)
+                .map(|(variant, v_ctx)| match &v_ctx.doc {
+                    Some(doc) => quote! { {
+                        let mut schema = #variant;
+                        schema.metadata.insert("description", #doc.into());
+                        schema
+                    } },
+                    None => variant,
+                })
+                .collect();
+
+            if ctx.single_variant_as_properties {
+                let variant = &variants[0];
+                let ident = &idents[0];
+
+                return Ok(parse_quote! { {
+                    let mut schema = #variant;
+                    if let SchemaType::Properties { properties, .. } = &mut schema.ty {
+                        properties.insert(#tag, Schema {
+                            ty: SchemaType::Enum {
+                                r#enum: [#ident].into(),
+                            },
+                            ..::jtd_derive::schema::Schema::default()
+                        });
+                    }
+                    schema
+                } });
             }
 
             Ok(parse_quote! {
@@ -259,10 +595,236 @@ fn gen_enum_schema(
     }
 }
 
+/// A C-style enum serialized as its discriminants, like with `serde_repr`.
+fn gen_repr_enum_schema(
+    ctx: &Container,
+    ident: &Ident,
+    enu: &DataEnum,
+    repr: &LitStr,
+) -> Result<TokenStream, syn::Error> {
+    let (ty, min, max): (Ident, i128, i128) = match repr.value().as_str() {
+        "int8" => (parse_quote!(Int8), i8::MIN.into(), i8::MAX.into()),
+        "uint8" => (parse_quote!(Uint8), u8::MIN.into(), u8::MAX.into()),
+        "int16" => (parse_quote!(Int16), i16::MIN.into(), i16::MAX.into()),
+        "uint16" => (parse_quote!(Uint16), u16::MIN.into(), u16::MAX.into()),
+        "int32" => (parse_quote!(Int32), i32::MIN.into(), i32::MAX.into()),
+        "uint32" => (parse_quote!(Uint32), u32::MIN.into(), u32::MAX.into()),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                repr,
+                "expected one of `int8`, `uint8`, `int16`, `uint16`, `int32`, `uint32`",
+            ))
+        }
+    };
+
+    if ctx.tag_type != context::TagType::External {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(repr = \"...\")] enums are serialized as plain integers, so they can't have a tag",
+        ));
+    }
+
+    if ctx.enum_values.is_some() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "can't set both `#[typedef(repr = \"...\")]` and `#[typedef(enum(...))]`",
+        ));
+    }
+
+    if let EnumKind::StructVariants = enum_kind(ident, enu)? {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(repr = \"...\")] requires all variants to be unit variants",
+        ));
+    }
+
+    // like rustc, start at 0 and count up from the last explicit discriminant
+    let mut next = 0;
+    let values: Vec<i64> = enu
+        .variants
+        .iter()
+        .map(|v| {
+            let value = match &v.discriminant {
+                Some((_, expr)) => parse_discriminant(expr)?,
+                None => next,
+            };
+
+            if value < min || value > max {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    format!("discriminant {} doesn't fit in `{}`", value, repr.value()),
+                ));
+            }
+
+            next = value + 1;
+            Ok(value as i64)
+        })
+        .collect_fallible()?;
+
+    Ok(parse_quote! {
+        Schema {
+            metadata: ::jtd_derive::schema::Metadata::from_map([(
+                "values",
+                ::serde_json::Value::Array(::std::vec![
+                    #(::serde_json::Value::from(#values)),*
+                ]),
+            )]),
+            ty: SchemaType::Type {
+                r#type: ::jtd_derive::schema::TypeSchema::#ty,
+            },
+            ..::jtd_derive::schema::Schema::default()
+        }
+    })
+}
+
+fn parse_discriminant(expr: &Expr) -> Result<i128, syn::Error> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_discriminant(expr).map(|v| -v),
+        Expr::Group(ExprGroup { expr, .. }) | Expr::Paren(ExprParen { expr, .. }) => {
+            parse_discriminant(expr)
+        }
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "#[typedef(repr = \"...\")] requires discriminants to be integer literals",
+        )),
+    }
+}
+
+/// _Typedef_ has no unions, so the best we can do is accept anything and list
+/// the schemas of the variants in metadata.
+fn gen_untagged_enum_schema(
+    ctx: &Container,
+    ident: &Ident,
+    enu: &DataEnum,
+) -> Result<TokenStream, syn::Error> {
+    if ctx.enum_values.is_some() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(enum(...))] is only allowed on enums with unit variants",
+        ));
+    }
+
+    let schemas: Vec<_> = enu
+        .variants
+        .iter()
+        .map(|v| match &v.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Ok(Field::from_syn_field(&fields.unnamed[0])?.schema())
+            }
+            _ => Err(syn::Error::new_spanned(
+                v,
+                "jtd-derive only supports untagged enums if all their variants have \
+                 exactly one unnamed field",
+            )),
+        })
+        .collect_fallible()?;
+
+    Ok(parse_quote! { {
+        gen.warn::<Self>("untagged enums have the empty schema, which accepts any value");
+        Schema {
+            metadata: ::jtd_derive::schema::Metadata::from_map([(
+                "untagged",
+                ::serde_json::Value::Array(::std::vec![
+                    #(::serde_json::to_value(#schemas).unwrap()),*
+                ]),
+            )]),
+            ..::jtd_derive::schema::Schema::default()
+        }
+    } })
+}
+
+/// Statements adding `doc` to the metadata of `schema` if the generator is
+/// producing input for `jtd-codegen`. Expects `gen` and `schema` to be in scope.
+fn gen_codegen_description(doc: &Option<String>) -> TokenStream {
+    let doc = doc.iter();
+    quote! {
+        #(
+            if gen.dialect() == ::jtd_derive::Dialect::JtdCodegen {
+                schema.metadata.insert("description", #doc.into());
+            }
+        )*
+    }
+}
+
 fn gen_metadata(meta: &HashMap<String, String>) -> TokenStream {
     let keys = meta.keys();
     let values = meta.values();
-    quote! { [#((#keys, #values.parse::<::serde_json::Value>().unwrap())),*] }
+    let len = meta.len();
+    quote! { {
+        let meta: [(&str, ::serde_json::Value); #len] =
+            [#((#keys, #values.parse::<::serde_json::Value>().unwrap())),*];
+        meta
+    } }
+}
+
+/// `deny_unknown_fields` always wins. Otherwise, the generator decides.
+fn gen_additional_properties(ctx: &Container) -> TokenStream {
+    if ctx.deny_unknown_fields {
+        quote! { false }
+    } else {
+        quote! { gen.default_additional_properties() }
+    }
+}
+
+/// A struct whose fields all have the same type, described as a map of that type.
+fn gen_values_schema(ident: &Ident, fields: &FieldsNamed) -> Result<TokenStream, syn::Error> {
+    let fields: Vec<_> = fields
+        .named
+        .iter()
+        .map(|f| Ok((f, Field::from_syn_field(f)?)))
+        .collect_fallible()?;
+    let fields: Vec<_> = fields.into_iter().filter(|(_, f)| !f.skip).collect();
+
+    if let Some((field, _)) = fields
+        .iter()
+        .find(|(_, f)| f.flatten || f.schema_with.is_some())
+    {
+        return Err(syn::Error::new_spanned(
+            field,
+            "#[typedef(as_values)] doesn't support `flatten` or `schema_with` fields",
+        ));
+    }
+
+    let Some(((_, first), rest)) = fields.split_first() else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(as_values)] requires at least one field that isn't skipped",
+        ));
+    };
+
+    // Types are compared as written, so e.g. an alias and the type it stands
+    // for are considered different.
+    let ty = &first.ty;
+    let ty_string = quote!(#ty).to_string();
+    if let Some((field, f)) = rest.iter().find(|(_, f)| {
+        let other = &f.ty;
+        quote!(#other).to_string() != ty_string
+    }) {
+        return Err(syn::Error::new_spanned(
+            &f.ty,
+            format!(
+                "#[typedef(as_values)] requires all fields to have the same type, but `{}` isn't `{}`",
+                field.ident.as_ref().unwrap(),
+                ty_string
+            ),
+        ));
+    }
+
+    Ok(quote! {
+        Schema {
+            ty: SchemaType::Values {
+                values: ::std::boxed::Box::new(gen.sub_schema::<#ty>()),
+            },
+            ..::jtd_derive::schema::Schema::default()
+        }
+    })
 }
 
 fn gen_named_fields(
@@ -270,46 +832,76 @@ fn gen_named_fields(
     fields: &FieldsNamed,
     rename_rule: Option<RenameRule>,
 ) -> Result<TokenStream, syn::Error> {
-    let fields: Vec<_> = fields
-        .named
+    let syn_fields = &fields.named;
+    let fields: Vec<Field> = syn_fields
         .iter()
         .map(Field::from_syn_field)
         .collect_fallible()?;
+    let fields = syn_fields.iter().zip(fields).filter(|(_, f)| !f.skip);
 
-    let mut idents: Vec<_> = fields.iter().map(|f| f.ident.clone()).collect();
-    let types: Vec<_> = fields.iter().map(|f| f.ty.clone()).collect();
-    let metas: Vec<_> = fields.into_iter().map(|f| gen_metadata(&f.meta)).collect();
+    let (flattened, fields): (Vec<_>, Vec<_>) = fields.partition(|(_, f)| f.flatten);
+    let flattened = flattened
+        .iter()
+        .map(|(_, f)| f.flatten_into_schema(ctx.default));
 
-    if let Some(rule) = rename_rule {
-        for ident in idents.iter_mut() {
-            *ident = rule.apply_to_field(&ident.to_string());
+    let idents: Vec<_> = fields
+        .iter()
+        .map(|(_, f)| match (&f.rename, rename_rule) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => rule.apply_to_field(f.ident.as_ref().unwrap()),
+            (None, None) => f.ident.clone().unwrap(),
+        })
+        .collect();
+
+    for (i, ((field, _), ident)) in fields.iter().zip(&idents).enumerate() {
+        if let Some(j) = idents[..i].iter().position(|other| other == ident) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!(
+                    "fields `{}` and `{}` are both represented as `{}`",
+                    fields[j].0.ident.as_ref().unwrap(),
+                    field.ident.as_ref().unwrap(),
+                    ident
+                ),
+            ));
         }
     }
 
-    let expanded_fields = quote! {#((#idents, {
-        let mut schema = gen.sub_schema::<#types>();
-        schema.metadata.extend(#metas);
-        schema
-    })),*};
-
-    let additional = !ctx.deny_unknown_fields;
-
-    let (prop, optional) = if ctx.default {
-        (quote! {[].into()}, quote! {[#expanded_fields].into()})
-    } else {
-        (quote! {[#expanded_fields].into()}, quote! {[].into()})
-    };
-
-    Ok(parse_quote! {
-        Schema {
+    let (optional, required): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .zip(idents)
+        .map(|((_, f), ident)| {
+            let schema = f.schema();
+            let doc = gen_codegen_description(&f.doc);
+            let meta = gen_metadata(&f.meta);
+
+            let expanded = quote! {(#ident, {
+                let mut schema = #schema;
+                #doc
+                schema.metadata.extend(#meta);
+                schema
+            })};
+
+            (ctx.default || f.optional, expanded)
+        })
+        .partition(|(optional, _)| *optional);
+
+    let prop = required.into_iter().map(|(_, expanded)| expanded);
+    let optional = optional.into_iter().map(|(_, expanded)| expanded);
+    let additional = gen_additional_properties(ctx);
+
+    Ok(parse_quote! { {
+        let mut schema = Schema {
             ty: SchemaType::Properties {
-                properties: #prop,
-                optional_properties: #optional,
+                properties: [#(#prop),*].into(),
+                optional_properties: [#(#optional),*].into(),
                 additional_properties: #additional,
             },
             ..::jtd_derive::schema::Schema::default()
-        }
-    })
+        };
+        #(#flattened)*
+        schema
+    } })
 }
 
 fn unwrap_fields_named(fields: &Fields) -> &FieldsNamed {
@@ -351,7 +943,9 @@ fn enum_kind(ident: &Ident, e: &DataEnum) -> Result<EnumKind, syn::Error> {
     match (named, unit) {
         (None, None) => Err(syn::Error::new_spanned(
             ident,
-            "jtd-derive does not support empty enums",
+            "jtd-derive does not support empty enums; an enum without variants is uninhabited, so no value of it can be serialized, \
+             and Typedef can't express a schema that nothing is valid against. \
+             If you only need `JsonTypedef` to satisfy a bound, implement it manually",
         )),
         (None, Some(_)) => Ok(EnumKind::UnitVariants),
         (Some(_), None) => Ok(EnumKind::StructVariants),
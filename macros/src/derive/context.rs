@@ -1,8 +1,10 @@
 mod container;
 mod field;
+mod variant;
 
 pub use container::Container;
 pub use field::FieldCtx;
+pub use variant::VariantCtx;
 
 use sdi::attr::RenameRule;
 use serde_derive_internals as sdi;
@@ -17,6 +19,8 @@ const SERDE_ATTR_IDENT: &str = "serde";
 pub enum TagType {
     External,
     Internal(String),
+    Adjacent { tag: String, content: String },
+    Untagged,
 }
 
 impl Default for TagType {
@@ -25,6 +29,67 @@ impl Default for TagType {
     }
 }
 
+/// The contents of the doc comments, one line per `#[doc = "..."]` attribute.
+fn collect_docs(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(s), ..
+            })) => Some(s.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+
+    let docs = lines.join("\n").trim().to_string();
+    (!docs.is_empty()).then_some(docs)
+}
+
+/// The error for a `#[typedef(...)]` parameter that isn't one of `known`. If it
+/// looks like a typo of one of them, that one is suggested.
+fn unknown_param(path: &syn::Path, known: &[&str]) -> syn::Error {
+    let name = path
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+
+    let suggestion = known
+        .iter()
+        .map(|candidate| (edit_distance(&name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= (name.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance);
+
+    let msg = match suggestion {
+        Some((_, candidate)) => format!(
+            "unknown jtd-derive parameter `{}`, did you mean `{}`?",
+            name, candidate
+        ),
+        None => format!("unknown jtd-derive parameter `{}`", name),
+    };
+
+    syn::Error::new_spanned(path, msg)
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut row: Vec<_> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev + usize::from(ca != *cb);
+            prev = row[j + 1];
+            row[j + 1] = substitution.min(prev + 1).min(row[j] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
 fn collect_attrs(
     attrs: &[Attribute],
     path: &str,
@@ -71,15 +136,26 @@ fn collect_attrs(
 }
 
 fn parse_rename_rule(args: impl Iterator<Item = Meta>) -> Option<RenameRule> {
-    let rename_all_args = args.filter(|meta| {
+    parse_serde_name(args, "rename_all").and_then(|rule| RenameRule::from_str(&rule).ok())
+}
+
+fn parse_rename(args: impl Iterator<Item = Meta>) -> Option<String> {
+    parse_serde_name(args, "rename")
+}
+
+/// Find the value of a serde parameter like `rename` that can be given either as
+/// `name = "..."` or `name(serialize = "...", deserialize = "...")`. In the latter
+/// case, the `deserialize` value is used.
+fn parse_serde_name(args: impl Iterator<Item = Meta>, name: &str) -> Option<String> {
+    let name_args = args.filter(|meta| {
         meta.path()
             .get_ident()
-            .map(|id| id.to_string().as_str() == "rename_all")
+            .map(|id| id.to_string().as_str() == name)
             .unwrap_or_default()
     });
 
-    rename_all_args
-        .filter_map(|meta| -> Option<RenameRule> {
+    name_args
+        .filter_map(|meta| -> Option<String> {
             match meta {
                 Meta::Path(_) => None,
                 Meta::List(l) => l
@@ -97,7 +173,7 @@ fn parse_rename_rule(args: impl Iterator<Item = Meta>) -> Option<RenameRule> {
                             }
 
                             if let Lit::Str(s) = &name_value.lit {
-                                RenameRule::from_str(&s.value()).ok()
+                                Some(s.value())
                             } else {
                                 None
                             }
@@ -108,7 +184,7 @@ fn parse_rename_rule(args: impl Iterator<Item = Meta>) -> Option<RenameRule> {
                     .last(),
                 Meta::NameValue(MetaNameValue { lit, .. }) => {
                     if let Lit::Str(s) = lit {
-                        RenameRule::from_str(&s.value()).ok()
+                        Some(s.value())
                     } else {
                         None
                     }
@@ -2,7 +2,12 @@ use std::collections::HashMap;
 
 use sdi::attr::RenameRule;
 use serde_derive_internals as sdi;
-use syn::{DeriveInput, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Type};
+use syn::parse::Parser as _;
+use syn::punctuated::Punctuated;
+use syn::{
+    DeriveInput, Lit, LitStr, Meta, MetaList, MetaNameValue, NestedMeta, Path, Token, Type,
+    WherePredicate,
+};
 
 use super::{collect_attrs, TagType, ATTR_IDENT, SERDE_ATTR_IDENT};
 use crate::iter_ext::IterExt as _;
@@ -18,11 +23,46 @@ pub struct Container {
     pub default: bool,
     pub rename_rule: Option<RenameRule>,
     pub metadata: HashMap<String, String>,
+    pub metadata_from: Option<Path>,
+    pub validate_with: Option<Path>,
+    pub enum_values: Option<Vec<LitStr>>,
+    pub single_variant_as_properties: bool,
+    pub repr: Option<LitStr>,
+    pub transparent_elements: Option<Type>,
+    pub as_values: bool,
+    pub map_key: bool,
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub doc: Option<String>,
 }
 
+/// Every `#[typedef(...)]` parameter understood here, for suggestions on typos.
+const CONTAINER_PARAMS: &[&str] = &[
+    "tag",
+    "untagged",
+    "repr",
+    "single_variant_as_properties",
+    "deny_unknown_fields",
+    "as_values",
+    "map_key",
+    "transparent",
+    "transparent_elements",
+    "bound",
+    "from",
+    "try_from",
+    "rename_all",
+    "default",
+    "metadata",
+    "metadata_from",
+    "validate_with",
+    "enum",
+];
+
 impl Container {
     pub fn from_input(input: &DeriveInput) -> Result<Self, syn::Error> {
-        let mut cont = Container::default();
+        let mut cont = Container {
+            doc: super::collect_docs(&input.attrs),
+            ..Container::default()
+        };
 
         let serde_ctx = sdi::Ctxt::new();
         let serde = sdi::attr::Container::from_ast(&serde_ctx, input);
@@ -33,10 +73,11 @@ impl Container {
         cont.tag_type = match serde.tag() {
             sdi::attr::TagType::External => TagType::External,
             sdi::attr::TagType::Internal { tag } => TagType::Internal(tag.clone()),
-            sdi::attr::TagType::Adjacent { .. } =>
-                return Err(syn::Error::new_spanned(&input.ident, "this type uses the adjacent enum representation, but `jtd_derive` doesn't support it")),
-            sdi::attr::TagType::None =>
-                return Err(syn::Error::new_spanned(&input.ident, "this type uses the untagged enum representation, but `jtd_derive` doesn't support it")),
+            sdi::attr::TagType::Adjacent { tag, content } => TagType::Adjacent {
+                tag: tag.clone(),
+                content: content.clone(),
+            },
+            sdi::attr::TagType::None => TagType::Untagged,
         };
         cont.deny_unknown_fields = serde.deny_unknown_fields();
         cont.transparent = serde.transparent();
@@ -72,6 +113,43 @@ impl Container {
                             ))
                         }
                     }
+                    "untagged" => {
+                        if let Meta::Path(_) = p {
+                            cont.tag_type = TagType::Untagged;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `untagged` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "repr" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                cont.repr = Some(s);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `repr = \"uint8\"`",
+                            ))
+                        }
+                    }
+                    "single_variant_as_properties" => {
+                        if let Meta::Path(_) = p {
+                            cont.single_variant_as_properties = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `single_variant_as_properties` parameter takes no value",
+                            ))
+                        }
+                    }
                     "deny_unknown_fields" => {
                         if let Meta::Path(_) = p {
                             cont.deny_unknown_fields = true;
@@ -83,6 +161,28 @@ impl Container {
                             ))
                         }
                     }
+                    "as_values" => {
+                        if let Meta::Path(_) = p {
+                            cont.as_values = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `as_values` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "map_key" => {
+                        if let Meta::Path(_) = p {
+                            cont.map_key = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `map_key` parameter takes no value",
+                            ))
+                        }
+                    }
                     "transparent" => {
                         if let Meta::Path(_) = p {
                             cont.transparent = true;
@@ -94,6 +194,40 @@ impl Container {
                             ))
                         }
                     }
+                    "transparent_elements" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                cont.transparent_elements = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `transparent_elements = \"ItemType\"`",
+                            ))
+                        }
+                    }
+                    "bound" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                cont.bound = Some(
+                                    Punctuated::parse_terminated
+                                        .parse_str(&s.value())
+                                        .map_err(|e| syn::Error::new_spanned(&s, e))?,
+                                );
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `bound = \"T::Output: JsonTypedef\"`",
+                            ))
+                        }
+                    }
                     "from" => {
                         if let Meta::NameValue(v) = p {
                             if let Lit::Str(s) = v.lit {
@@ -195,13 +329,87 @@ impl Container {
                             ))
                         }
                     }
-                    _ => Err(syn::Error::new_spanned(
-                        p.path(),
-                        "unknown jtd-derive parameter",
-                    )),
+                    "metadata_from" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                cont.metadata_from = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `metadata_from = \"path::to::fn\"`",
+                            ))
+                        }
+                    }
+                    "validate_with" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                cont.validate_with = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `validate_with = \"path::to::fn\"`",
+                            ))
+                        }
+                    }
+                    "enum" => {
+                        if let Meta::List(MetaList { nested, .. }) = &p {
+                            let values: Vec<_> = nested
+                                .iter()
+                                .map(|nested_meta| {
+                                    if let NestedMeta::Lit(Lit::Str(s)) = nested_meta {
+                                        Ok(s.clone())
+                                    } else {
+                                        Err(syn::Error::new_spanned(
+                                            nested_meta,
+                                            "expected string literal",
+                                        ))
+                                    }
+                                })
+                                .collect_fallible()?;
+
+                            if values.is_empty() {
+                                return Err(syn::Error::new_spanned(
+                                    p,
+                                    "the `enum` parameter requires at least one value",
+                                ));
+                            }
+
+                            values
+                                .iter()
+                                .enumerate()
+                                .map(|(i, v)| {
+                                    if values[..i].iter().any(|other| other.value() == v.value()) {
+                                        Err(syn::Error::new_spanned(
+                                            v,
+                                            format!("duplicate enum value `{}`", v.value()),
+                                        ))
+                                    } else {
+                                        Ok(())
+                                    }
+                                })
+                                .collect_fallible::<Vec<_>>()?;
+
+                            cont.enum_values = Some(values);
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `enum(\"a\", \"b\")`",
+                            ))
+                        }
+                    }
+                    _ => Err(super::unknown_param(p.path(), CONTAINER_PARAMS)),
                 }
             })
-            .collect_fallible()?;
+            .collect_fallible::<()>()?;
 
         Ok(cont)
     }
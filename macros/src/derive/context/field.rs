@@ -1,18 +1,44 @@
 use std::collections::HashMap;
 
-use syn::{Field, Lit, Meta, MetaList, MetaNameValue, NestedMeta};
+use syn::{Field, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path, Type};
 
-use super::{collect_attrs, ATTR_IDENT};
+use super::{collect_attrs, ATTR_IDENT, SERDE_ATTR_IDENT};
 use crate::iter_ext::IterExt as _;
 
 #[derive(Default)]
 pub struct FieldCtx {
     pub metadata: HashMap<String, String>,
+    pub schema_with: Option<Path>,
+    pub ty: Option<Type>,
+    pub optional: bool,
+    pub rename: Option<String>,
+    pub flatten: bool,
+    pub skip: bool,
+    pub doc: Option<String>,
 }
 
+/// Every `#[typedef(...)]` parameter understood here, for suggestions on typos.
+const FIELD_PARAMS: &[&str] = &[
+    "metadata",
+    "group",
+    "rename",
+    "skip",
+    "flatten",
+    "optional",
+    "schema_with",
+    "type",
+];
+
 impl FieldCtx {
     pub fn from_input(input: &Field) -> Result<Self, syn::Error> {
-        let mut field = Self::default();
+        let mut field = Self {
+            rename: super::parse_rename(collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?),
+            flatten: collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?
+                .any(|meta| meta.path().is_ident("flatten")),
+            skip: is_serde_skipped(collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?),
+            doc: super::collect_docs(&input.attrs),
+            ..Self::default()
+        };
 
         let params = collect_attrs(&input.attrs, ATTR_IDENT)?;
         params
@@ -28,7 +54,7 @@ impl FieldCtx {
                 {
                     "metadata" => {
                         if let Meta::List(MetaList { nested, .. }) = p {
-                            let metadata = nested
+                            let metadata: HashMap<_, _> = nested
                                 .into_iter()
                                 .map(|nested_meta| {
                                     if let NestedMeta::Meta(Meta::NameValue(MetaNameValue {
@@ -60,7 +86,7 @@ impl FieldCtx {
                                 })
                                 .collect_fallible()?;
 
-                            field.metadata = metadata;
+                            field.metadata.extend(metadata);
                             Ok(())
                         } else {
                             Err(syn::Error::new_spanned(
@@ -69,14 +95,145 @@ impl FieldCtx {
                             ))
                         }
                     }
-                    _ => Err(syn::Error::new_spanned(
-                        p.path(),
-                        "unknown jtd-derive parameter",
-                    )),
+                    "group" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                // metadata values are stored as JSON
+                                let value = serde_json::to_string(&s.value())
+                                    .expect("strings are always serializable");
+                                field.metadata.insert("group".to_string(), value);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `group = \"...\"`",
+                            ))
+                        }
+                    }
+                    "rename" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                field.rename = Some(s.value());
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `rename = \"...\"`",
+                            ))
+                        }
+                    }
+                    "skip" => {
+                        if let Meta::Path(_) = p {
+                            field.skip = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `skip` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "flatten" => {
+                        if let Meta::Path(_) = p {
+                            field.flatten = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `flatten` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "optional" => {
+                        if let Meta::Path(_) = p {
+                            field.optional = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `optional` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "schema_with" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                field.schema_with = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `schema_with = \"path::to::fn\"`",
+                            ))
+                        }
+                    }
+                    "type" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                field.ty = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `type = \"SomeType\"`",
+                            ))
+                        }
+                    }
+                    _ => Err(super::unknown_param(p.path(), FIELD_PARAMS)),
                 }
             })
-            .collect_fallible()?;
+            .collect_fallible::<()>()?;
+
+        if field.schema_with.is_some() && field.ty.is_some() {
+            return Err(syn::Error::new_spanned(
+                input,
+                "can't set both `#[typedef(schema_with = \"...\")]` and `#[typedef(type = \"...\")]`",
+            ));
+        }
+
+        // serde might serialize the field in a way that has nothing to do with
+        // its type. We can't know how, so the user has to tell us.
+        if field.schema_with.is_none() && field.ty.is_none() {
+            if let Some(with) = collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?.find(|meta| {
+                let path = meta.path();
+                path.is_ident("with")
+                    || path.is_ident("serialize_with")
+                    || path.is_ident("deserialize_with")
+            }) {
+                return Err(syn::Error::new_spanned(
+                    with,
+                    "this field uses custom serialization, so jtd-derive can't infer its schema; \
+                     specify it with `#[typedef(schema_with = \"path::to::fn\")]` \
+                     or `#[typedef(type = \"SomeType\")]`",
+                ));
+            }
+        }
 
         Ok(field)
     }
 }
+
+/// A field that's neither serialized nor deserialized doesn't belong in the schema.
+fn is_serde_skipped(params: impl Iterator<Item = Meta>) -> bool {
+    let (mut ser, mut de) = (false, false);
+    for p in params {
+        if p.path().is_ident("skip") {
+            return true;
+        }
+        ser |= p.path().is_ident("skip_serializing");
+        de |= p.path().is_ident("skip_deserializing");
+    }
+    ser && de
+}
@@ -0,0 +1,106 @@
+use sdi::attr::RenameRule;
+use serde_derive_internals as sdi;
+use syn::{Lit, Meta, Variant};
+
+use super::{collect_attrs, ATTR_IDENT, SERDE_ATTR_IDENT};
+use crate::iter_ext::IterExt as _;
+
+#[derive(Default)]
+pub struct VariantCtx {
+    pub tag_value: Option<String>,
+    pub rename: Option<String>,
+    pub rename_rule: Option<RenameRule>,
+    pub doc: Option<String>,
+    pub null_variant: bool,
+}
+
+/// Every `#[typedef(...)]` parameter understood here, for suggestions on typos.
+const VARIANT_PARAMS: &[&str] = &["rename", "rename_all", "tag_value", "null_variant"];
+
+impl VariantCtx {
+    pub fn from_input(input: &Variant) -> Result<Self, syn::Error> {
+        let mut variant = Self {
+            rename: super::parse_rename(collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?),
+            rename_rule: super::parse_rename_rule(collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?),
+            doc: super::collect_docs(&input.attrs),
+            ..Self::default()
+        };
+
+        let params = collect_attrs(&input.attrs, ATTR_IDENT)?;
+        params
+            .map(|p| {
+                match p
+                    .path()
+                    .get_ident()
+                    .ok_or_else(|| {
+                        syn::Error::new_spanned(p.path(), "jtd-derive parameter must be an ident")
+                    })?
+                    .to_string()
+                    .as_str()
+                {
+                    "rename" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                variant.rename = Some(s.value());
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `rename = \"...\"`",
+                            ))
+                        }
+                    }
+                    "rename_all" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = &v.lit {
+                                let rule = RenameRule::from_str(&s.value())
+                                    .map_err(|e| syn::Error::new_spanned(v.lit, e))?;
+                                variant.rename_rule = Some(rule);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `rename_all = \"camelCase\"`",
+                            ))
+                        }
+                    }
+                    "tag_value" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                variant.tag_value = Some(s.value());
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `tag_value = \"...\"`",
+                            ))
+                        }
+                    }
+                    "null_variant" => {
+                        if let Meta::Path(_) = p {
+                            variant.null_variant = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `null_variant` parameter takes no value",
+                            ))
+                        }
+                    }
+                    _ => Err(super::unknown_param(p.path(), VARIANT_PARAMS)),
+                }
+            })
+            .collect_fallible::<()>()?;
+
+        Ok(variant)
+    }
+}
@@ -1,23 +1,158 @@
 use std::collections::HashMap;
 
-use syn::Type;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{GenericArgument, Path, PathArguments, Type, TypePath};
 
 use super::context::FieldCtx;
 
 pub struct Field {
     pub ty: Type,
-    pub ident: String,
+    pub ident: Option<String>,
     pub meta: HashMap<String, String>,
+    pub schema_with: Option<Path>,
+    pub optional: bool,
+    pub rename: Option<String>,
+    pub flatten: bool,
+    pub skip: bool,
+    pub doc: Option<String>,
 }
 
 impl Field {
     pub fn from_syn_field(f: &syn::Field) -> Result<Self, syn::Error> {
         let ctx = FieldCtx::from_input(&f)?;
+        let mut ty = ctx.ty.unwrap_or_else(|| f.ty.clone());
+
+        // An optional field is either missing or holds a value, so the outer
+        // `Option` is expressed by the field being optional. Any other `Option`
+        // inside makes the value nullable as usual.
+        let mut optional = ctx.optional;
+        if ctx.schema_with.is_none() {
+            match peel_option(&ty).cloned() {
+                Some(inner) if ctx.optional => ty = inner,
+                None if ctx.optional => {
+                    return Err(syn::Error::new_spanned(
+                        &ty,
+                        "#[typedef(optional)] requires the field to be an `Option`, \
+                         possibly inside `Box`, `Rc` or `Arc`",
+                    ))
+                }
+                // A flattened `None` contributes no fields at all, so all the
+                // fields it might contribute are optional.
+                Some(inner) if ctx.flatten => {
+                    ty = inner;
+                    optional = true;
+                }
+                _ => (),
+            }
+        }
 
         Ok(Self {
-            ty: f.ty.clone(),
-            ident: f.ident.as_ref().map(|i| i.to_string()).unwrap(),
+            ty,
+            ident: f.ident.as_ref().map(|i| i.to_string()),
             meta: ctx.metadata,
+            schema_with: ctx.schema_with,
+            optional,
+            rename: ctx.rename,
+            flatten: ctx.flatten,
+            skip: ctx.skip,
+            doc: ctx.doc,
         })
     }
+
+    /// An expression producing the schema of this field. Expects `gen` to be
+    /// in scope.
+    pub fn schema(&self) -> TokenStream {
+        match &self.schema_with {
+            Some(path) => quote! { #path(gen) },
+            None => {
+                let ty = &self.ty;
+                quote! { gen.sub_schema::<#ty>() }
+            }
+        }
+    }
+}
+
+impl Field {
+    /// Statements merging the properties of this flattened field into `schema`,
+    /// which is expected to be a properties schema. Expects `gen` to be in scope.
+    pub fn flatten_into_schema(&self, all_optional: bool) -> TokenStream {
+        let ident = self.ident.as_deref().unwrap_or_default();
+        let flat_schema = match &self.schema_with {
+            Some(path) => quote! { #path(gen) },
+            // this has to be the schema itself rather than a ref to it
+            None => {
+                let ty = &self.ty;
+                quote! { <#ty as ::jtd_derive::JsonTypedef>::schema(gen) }
+            }
+        };
+        let target = if all_optional || self.optional {
+            quote! { optional_properties }
+        } else {
+            quote! { properties }
+        };
+
+        quote! {
+            match #flat_schema.ty {
+                SchemaType::Properties {
+                    properties: flat_properties,
+                    optional_properties: flat_optional_properties,
+                    ..
+                } => {
+                    if let SchemaType::Properties {
+                        properties,
+                        optional_properties,
+                        ..
+                    } = &mut schema.ty
+                    {
+                        #target.extend(flat_properties);
+                        optional_properties.extend(flat_optional_properties);
+                    }
+                }
+                // the extra entries of a flattened map can't be typed, only allowed
+                SchemaType::Values { .. } => {
+                    if let SchemaType::Properties {
+                        additional_properties,
+                        ..
+                    } = &mut schema.ty
+                    {
+                        *additional_properties = true;
+                    }
+                }
+                // e.g. an internally tagged enum, which serde can flatten but
+                // JSON Typedef can't merge into a properties schema
+                _ => gen.reject::<Self>(format!(
+                    "the flattened field `{}` must have a schema in the properties or values form",
+                    #ident
+                )),
+            }
+        }
+    }
+}
+
+/// Find the outermost `Option` in `ty`, looking through the smart pointers whose
+/// schema is that of their content, and return the type it wraps.
+///
+/// This is purely syntactic, so it doesn't see through type aliases.
+fn peel_option(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last()?,
+        Type::Group(group) => return peel_option(&group.elem),
+        Type::Paren(paren) => return peel_option(&paren.elem),
+        _ => return None,
+    };
+
+    let inner = match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => inner,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    match segment.ident.to_string().as_str() {
+        "Option" => Some(inner),
+        "Box" | "Rc" | "Arc" => peel_option(inner),
+        _ => None,
+    }
 }
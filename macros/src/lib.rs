@@ -15,8 +15,14 @@ use syn::{parse_macro_input, DeriveInput};
 pub fn query_responses_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    let map_key_impl = derive::derive_map_key(&input);
+
     let expanded = match derive::derive(input) {
-        Ok(item_impl) => item_impl.into_token_stream(),
+        Ok(item_impl) => {
+            let mut expanded = item_impl.into_token_stream();
+            expanded.extend(map_key_impl.into_token_stream());
+            expanded
+        }
         Err(e) => e.into_compile_error(),
     };
 
@@ -0,0 +1,94 @@
+//! Helpers for embedding schemas in a crate from its build script.
+//!
+//! ```no_run
+//! // build.rs
+//! # mod my_api { #[derive(jtd_derive::JsonTypedef)] pub struct User { pub id: u32 } }
+//! use jtd_derive::build::{emit_schemas, to_schema_string};
+//!
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("schemas.rs");
+//! emit_schemas(
+//!     &[("User", to_schema_string::<my_api::User>().unwrap())],
+//!     out,
+//! )
+//! .unwrap();
+//!
+//! // lib.rs
+//! // include!(concat!(env!("OUT_DIR"), "/schemas.rs"));
+//! // assert!(USER_SCHEMA.starts_with('{'));
+//! ```
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+use crate::{GenError, JsonTypedef};
+
+/// Generate the schema of `T` with [`JsonTypedef::schema_default`] and serialize it with
+/// [`RootSchema::to_pretty_canonical_string`](crate::schema::RootSchema::to_pretty_canonical_string),
+/// so that it doesn't change unless the schema does.
+pub fn to_schema_string<T: JsonTypedef>() -> Result<String, GenError> {
+    T::schema_default().map(|root| root.to_pretty_canonical_string())
+}
+
+/// Write a Rust file to `out_path` with a `pub const <NAME>_SCHEMA: &str` for
+/// every `(name, schema_json)` pair, in the given order. `name` is converted to
+/// `SCREAMING_SNAKE_CASE`, so `"UserProfile"` becomes `USER_PROFILE_SCHEMA`.
+///
+/// The file is only written if its contents would change, so that Cargo doesn't
+/// rebuild the crate needlessly. Fails with [`io::ErrorKind::InvalidInput`] if two
+/// names map to the same constant.
+pub fn emit_schemas<N: AsRef<str>, S: AsRef<str>>(
+    schemas: &[(N, S)],
+    out_path: impl AsRef<Path>,
+) -> io::Result<()> {
+    let mut consts = HashSet::new();
+    let mut code = String::from("// @generated by jtd_derive::build::emit_schemas\n");
+
+    for (name, schema) in schemas {
+        let ident = const_ident(name.as_ref());
+        if !consts.insert(ident.clone()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("more than one schema would be called `{}`", ident),
+            ));
+        }
+
+        code.push_str(&format!(
+            "\npub const {}: &str = {:?};\n",
+            ident,
+            schema.as_ref()
+        ));
+    }
+
+    let out_path = out_path.as_ref();
+    if std::fs::read_to_string(out_path).ok().as_deref() != Some(code.as_str()) {
+        std::fs::write(out_path, code)?;
+    }
+
+    Ok(())
+}
+
+fn const_ident(name: &str) -> String {
+    let mut ident = String::new();
+    let mut prev: Option<char> = None;
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            if c.is_uppercase() && matches!(prev, Some(p) if p.is_lowercase() || p.is_numeric()) {
+                ident.push('_');
+            }
+            ident.extend(c.to_uppercase());
+        } else if !ident.is_empty() && !ident.ends_with('_') {
+            ident.push('_');
+        }
+        prev = Some(c);
+    }
+
+    if ident.starts_with(|c: char| c.is_numeric()) {
+        ident.insert(0, '_');
+    }
+    if !ident.is_empty() && !ident.ends_with('_') {
+        ident.push('_');
+    }
+    ident.push_str("SCHEMA");
+    ident
+}
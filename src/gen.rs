@@ -2,11 +2,14 @@
 
 mod naming_strategy;
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Debug;
 
 use self::naming_strategy::NamingStrategy;
-use crate::schema::{RootSchema, Schema, SchemaType};
+use crate::registry::Entry;
+use crate::schema::{
+    resolve_ref, OrderedRootSchema, RootSchema, Schema, SchemaType, TypeSchema, ValidationError,
+};
 use crate::type_id::{type_id, TypeId};
 use crate::{JsonTypedef, Names};
 
@@ -78,6 +81,25 @@ pub struct Generator {
     refs: HashSet<TypeId>,
     definitions: HashMap<TypeId, (Names, DefinitionState)>,
     inlining: Inlining,
+    deny_additional_properties: bool,
+    int_ranges: bool,
+    nullable_metadata: bool,
+    hash: bool,
+    ref_style: RefStyle,
+    tag_in_variants: bool,
+    /// The first type that needed a ref despite refs being forbidden.
+    forbidden_ref: Option<String>,
+    /// The first type whose schema was rejected, and why.
+    rejected: Option<(String, String)>,
+    dedup_threshold: Option<usize>,
+    /// `None` unless warnings were requested with [`GeneratorBuilder::collect_warnings`].
+    warnings: Option<Vec<String>>,
+    definition_order: DefinitionOrder,
+    /// Every type a definition was created for, in the order they were first seen.
+    insertion_order: Vec<TypeId>,
+    strict: bool,
+    dialect: Dialect,
+    big_ints: BigInts,
 }
 
 impl Generator {
@@ -86,49 +108,217 @@ impl Generator {
         GeneratorBuilder::default()
     }
 
+    /// A `Generator` with the default settings that also
+    /// [validates](GeneratorBuilder::strict) everything it generates.
+    pub fn strict() -> Self {
+        Self::builder().strict().build()
+    }
+
     /// Generate the root schema for the given type according to the settings.
     /// This consumes the generator.
     ///
     /// This will return an error if a naming collision is detected, i.e. two
     /// distinct Rust types produce the same identifier.
-    pub fn into_root_schema<T: JsonTypedef>(mut self) -> Result<RootSchema, GenError> {
-        let schema = self.sub_schema_impl::<T>(true);
-        self.clean_up_defs();
+    pub fn into_root_schema<T: JsonTypedef>(self) -> Result<RootSchema, GenError> {
+        self.into_root_schema_with_warnings::<T>()
+            .map(|(root, _)| root)
+    }
 
-        fn process_defs(
-            defs: HashMap<TypeId, (Names, DefinitionState)>,
-            ns: &mut NamingStrategy,
-        ) -> Result<BTreeMap<String, Schema>, GenError> {
-            // This could probably be optimized somehow.
+    /// Like [`Generator::into_root_schema`], but also returns the
+    /// [warnings](Generator::warnings) collected along the way.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use jtd_derive::Generator;
+    ///
+    /// let (_, warnings) = Generator::builder()
+    ///     .collect_warnings()
+    ///     .build()
+    ///     .into_root_schema_with_warnings::<HashSet<u32>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(warnings.len(), 1);
+    /// ```
+    pub fn into_root_schema_with_warnings<T: JsonTypedef>(
+        self,
+    ) -> Result<(RootSchema, Vec<String>), GenError> {
+        self.generate::<T>()
+            .map(|(ordered, warnings)| (ordered.into_root(), warnings))
+    }
 
-            let defs = defs
-                .into_iter()
-                .map(|(_, (n, s))| (ns.fun()(&n), (n, s.unwrap())));
+    /// Like [`Generator::into_root_schema`], but keeps the order of the
+    /// definitions set with [`GeneratorBuilder::definition_order`]. The
+    /// [`RootSchema`] itself always has its definitions sorted by name.
+    ///
+    /// ```
+    /// use jtd_derive::{DefinitionOrder, Generator, JsonTypedef};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Outer {
+    ///     inner: Inner,
+    /// }
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Inner {
+    ///     x: u32,
+    /// }
+    ///
+    /// let ordered = Generator::builder()
+    ///     .naming_short()
+    ///     .top_level_ref()
+    ///     .definition_order(DefinitionOrder::Insertion)
+    ///     .build()
+    ///     .into_ordered_root_schema::<Outer>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(ordered.definition_order().collect::<Vec<_>>(), ["Outer", "Inner"]);
+    /// ```
+    pub fn into_ordered_root_schema<T: JsonTypedef>(self) -> Result<OrderedRootSchema, GenError> {
+        self.generate::<T>().map(|(ordered, _)| ordered)
+    }
 
-            let mut map = HashMap::new();
+    fn generate<T: JsonTypedef>(mut self) -> Result<(OrderedRootSchema, Vec<String>), GenError> {
+        let schema = self.sub_schema_impl::<T>(true);
+        self.finish(schema, vec![])
+    }
 
-            for (key, (names, schema)) in defs {
-                if let Some((other_names, _)) = map.get(&key) {
-                    return Err(GenError::NameCollision {
-                        id: key,
-                        type1: NamingStrategy::long().fun()(other_names),
-                        type2: NamingStrategy::long().fun()(&names),
-                    });
-                } else {
-                    map.insert(key, (names, schema));
+    /// Generate a root schema with an empty top-level schema and the given
+    /// registry entries as definitions, alongside any definitions they need.
+    ///
+    /// This will return an error if one of the registered names collides with
+    /// a definition generated for some type.
+    pub(crate) fn into_bundle<'a>(
+        mut self,
+        entries: impl IntoIterator<Item = (&'a str, Entry)>,
+    ) -> Result<OrderedRootSchema, GenError> {
+        let entries: Vec<_> = entries
+            .into_iter()
+            .filter_map(|(name, entry)| match entry {
+                Entry::Named(schema_fn) => Some((name.to_string(), schema_fn(&mut self))),
+                Entry::Type(define_fn) => {
+                    define_fn(&mut self).map(|schema| (name.to_string(), schema))
                 }
-            }
+            })
+            .collect();
+
+        self.finish(Schema::default(), entries)
+            .map(|(ordered, _)| ordered)
+    }
+
+    /// Turn everything generated so far into the final root schema, with
+    /// `entries` as extra named definitions. This is where all the
+    /// post-processing configured in the builder happens.
+    fn finish(
+        mut self,
+        schema: Schema,
+        entries: Vec<(String, Schema)>,
+    ) -> Result<(OrderedRootSchema, Vec<String>), GenError> {
+        let hash = self.hash;
+        let warnings = self.warnings.take().unwrap_or_default();
+        let dedup_threshold = match self.inlining {
+            Inlining::Only => None,
+            _ => self.dedup_threshold,
+        };
+        let ref_style = self.ref_style;
+        let definition_order = self.definition_order;
+        let strict = self.strict;
+        let insertion_order: Vec<_> = self
+            .insertion_order
+            .iter()
+            .filter(|id| self.refs.contains(id))
+            .map(|id| self.naming_strategy.fun()(&self.definitions[id].0))
+            .collect();
+
+        let defs = self.into_definitions()?;
 
-            Ok(map
+        if let Some((name, (names, _))) = entries
+            .iter()
+            .find_map(|(name, _)| defs.get_key_value(name))
+        {
+            return Err(GenError::NameCollision {
+                id: name.clone(),
+                type1: name.clone(),
+                type2: NamingStrategy::long().fun()(names),
+            });
+        }
+
+        let mut root = RootSchema {
+            definitions: defs
                 .into_iter()
                 .map(|(key, (_, schema))| (key, schema))
-                .collect())
+                .chain(entries)
+                .collect(),
+            schema,
+        };
+
+        if let Some(threshold) = dedup_threshold {
+            dedup_anonymous(&mut root, threshold, ref_style);
         }
 
-        Ok(RootSchema {
-            definitions: process_defs(self.definitions, &mut self.naming_strategy)?,
-            schema,
-        })
+        let order = match definition_order {
+            DefinitionOrder::Alphabetical => vec![],
+            DefinitionOrder::Insertion => insertion_order,
+            DefinitionOrder::Topological => topological_order(&root.definitions),
+        };
+
+        if strict {
+            root.validate()?;
+        }
+
+        if hash {
+            insert_hash(&mut root);
+        }
+
+        Ok((OrderedRootSchema::new(root, order), warnings))
+    }
+
+    /// Make sure `T` ends up in the definitions, even if nothing refers to it.
+    /// Types that can't be referenced have no definition of their own, so their
+    /// schema is returned instead.
+    pub(crate) fn define<T: JsonTypedef>(&mut self) -> Option<Schema> {
+        let schema = self.sub_schema_impl::<T>(false);
+        if !T::referenceable() {
+            return Some(schema);
+        }
+
+        self.refs.insert(type_id::<T>());
+        None
+    }
+
+    fn into_definitions(mut self) -> Result<HashMap<String, (Names, Schema)>, GenError> {
+        if let Some(type_name) = self.forbidden_ref {
+            return Err(GenError::RefRequired { type_name });
+        }
+
+        if let Some((type_name, message)) = self.rejected {
+            return Err(GenError::Rejected { type_name, message });
+        }
+
+        self.clean_up_defs();
+
+        // This could probably be optimized somehow.
+
+        let ns = &self.naming_strategy;
+        let defs = self
+            .definitions
+            .into_iter()
+            .map(|(_, (n, s))| (ns.fun()(&n), (n, s.unwrap())));
+
+        let mut map = HashMap::new();
+
+        for (key, (names, schema)) in defs {
+            if let Some((other_names, _)) = map.get(&key) {
+                return Err(GenError::NameCollision {
+                    id: key,
+                    type1: NamingStrategy::long().fun()(other_names),
+                    type2: NamingStrategy::long().fun()(&names),
+                });
+            } else {
+                map.insert(key, (names, schema));
+            }
+        }
+
+        Ok(map)
     }
 
     /// Generate a [`Schema`] for a given type, adding definitions to the
@@ -141,10 +331,86 @@ impl Generator {
         self.sub_schema_impl::<T>(false)
     }
 
+    /// Whether `additionalProperties` should be allowed for types that don't
+    /// specify it themselves. This is `true` unless configured otherwise with
+    /// [`GeneratorBuilder::default_additional_properties`].
+    ///
+    /// Like [`Generator::sub_schema`], this is meant for implementors of
+    /// [`JsonTypedef`].
+    pub fn default_additional_properties(&self) -> bool {
+        !self.deny_additional_properties
+    }
+
+    /// Record that the schema of `T` can't capture something about how it's
+    /// serialized. This does nothing unless warnings were requested with
+    /// [`GeneratorBuilder::collect_warnings`]. The warning starts with the long
+    /// form of [`T::names`](JsonTypedef::names), which for types that aren't
+    /// referenceable describes their schema rather than the Rust type, so
+    /// `message` should say what the problem is on its own.
+    ///
+    /// Like [`Generator::sub_schema`], this is meant for implementors of
+    /// [`JsonTypedef`].
+    pub fn warn<T: JsonTypedef + ?Sized>(&mut self, message: &str) {
+        if let Some(warnings) = &mut self.warnings {
+            let warning = format!(
+                "`{}`: {}",
+                NamingStrategy::long().fun()(&T::names()),
+                message
+            );
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+    }
+
+    /// Make generation fail with [`GenError::Rejected`] because the schema of
+    /// `T` is unacceptable. Only the first rejection is reported. This is what
+    /// `#[typedef(validate_with = "...")]` uses. Like in [`Generator::warn`],
+    /// the type is named by the long form of [`T::names`](JsonTypedef::names).
+    ///
+    /// Like [`Generator::sub_schema`], this is meant for implementors of
+    /// [`JsonTypedef`].
+    pub fn reject<T: JsonTypedef + ?Sized>(&mut self, message: impl Into<String>) {
+        self.rejected
+            .get_or_insert_with(|| (NamingStrategy::long().fun()(&T::names()), message.into()));
+    }
+
+    /// The consumer the schema is tailored to. See [`Dialect`].
+    ///
+    /// Like [`Generator::sub_schema`], this is meant for implementors of
+    /// [`JsonTypedef`].
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// How integer types too big for any _Typedef_ type are handled. See
+    /// [`BigInts`].
+    ///
+    /// Like [`Generator::sub_schema`], this is meant for implementors of
+    /// [`JsonTypedef`].
+    pub fn big_ints(&self) -> BigInts {
+        self.big_ints
+    }
+
+    /// The number of definitions created so far, i.e. one for each type that had
+    /// to be referenced. Once the generator is consumed, the final count is the
+    /// length of [`RootSchema::definitions`], which also includes any schemas
+    /// hoisted by [`GeneratorBuilder::dedup_anonymous`].
+    pub fn definitions_count(&self) -> usize {
+        self.refs.len()
+    }
+
+    /// The warnings collected so far about lossy mappings, e.g. sets whose
+    /// elements' uniqueness isn't captured by the schema. Always empty unless
+    /// requested with [`GeneratorBuilder::collect_warnings`].
+    pub fn warnings(&self) -> &[String] {
+        self.warnings.as_deref().unwrap_or_default()
+    }
+
     fn sub_schema_impl<T: JsonTypedef + ?Sized>(&mut self, top_level: bool) -> Schema {
         let id = type_id::<T>();
         let inlining = match self.inlining {
-            Inlining::Always => true,
+            Inlining::Always | Inlining::Only => true,
             Inlining::Normal => top_level,
             Inlining::Never => false,
         };
@@ -169,7 +435,8 @@ impl Generator {
                 if T::referenceable() {
                     self.definitions
                         .insert(id, (T::names(), DefinitionState::Processing));
-                    let schema = T::schema(self);
+                    self.insertion_order.push(id);
+                    let schema = self.build::<T>();
                     self.definitions
                         .get_mut(&id)
                         .unwrap()
@@ -178,15 +445,22 @@ impl Generator {
 
                     (inlining && !self.refs.contains(&id)).then_some(schema)
                 } else {
-                    Some(T::schema(self))
+                    Some(self.build::<T>())
                 }
             }
         };
 
         inlined_schema.unwrap_or_else(|| {
+            if let Inlining::Only = self.inlining {
+                self.forbidden_ref
+                    .get_or_insert_with(|| NamingStrategy::long().fun()(&T::names()));
+            }
+
             let schema = Schema {
                 ty: SchemaType::Ref {
-                    r#ref: self.naming_strategy.fun()(&T::names()),
+                    r#ref: self
+                        .ref_style
+                        .apply(self.naming_strategy.fun()(&T::names())),
                 },
                 ..Schema::default()
             };
@@ -195,6 +469,47 @@ impl Generator {
         })
     }
 
+    fn build<T: JsonTypedef + ?Sized>(&mut self) -> Schema {
+        let mut schema = T::schema(self);
+
+        if self.tag_in_variants {
+            if let SchemaType::Discriminator {
+                discriminator,
+                mapping,
+            } = &mut schema.ty
+            {
+                for variant in mapping.values_mut() {
+                    if let SchemaType::Properties { properties, .. } = &mut variant.ty {
+                        properties.insert(
+                            discriminator,
+                            Schema {
+                                ty: SchemaType::Type {
+                                    r#type: TypeSchema::String,
+                                },
+                                ..Schema::default()
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        if self.int_ranges {
+            if let SchemaType::Type { r#type } = &schema.ty {
+                if let Some((min, max)) = int_range(r#type) {
+                    schema.metadata.insert("minimum", min.into());
+                    schema.metadata.insert("maximum", max.into());
+                }
+            }
+        }
+
+        if self.nullable_metadata && schema.nullable {
+            schema.metadata.insert("nullable", true.into());
+        }
+
+        schema
+    }
+
     fn clean_up_defs(&mut self) {
         let to_remove: Vec<_> = self
             .definitions
@@ -209,8 +524,178 @@ impl Generator {
     }
 }
 
+/// Hoist object schemas that occur more than `threshold` times into definitions.
+/// The biggest repeated schema goes first, so that schemas nested in it aren't
+/// counted once per copy.
+fn dedup_anonymous(root: &mut RootSchema, threshold: usize, ref_style: RefStyle) {
+    // nullability stays with each occurrence
+    fn key(schema: &Schema) -> Option<String> {
+        match schema.ty {
+            SchemaType::Properties { .. } | SchemaType::Discriminator { .. } => Some(
+                serde_json::to_string(&Schema {
+                    nullable: false,
+                    ..schema.clone()
+                })
+                .expect("schemas are always serializable"),
+            ),
+            _ => None,
+        }
+    }
+
+    // mapping entries must be inline properties schemas, so they're never
+    // hoisted themselves, only the schemas nested in them
+    fn is_mapping(schema: &Schema) -> bool {
+        matches!(schema.ty, SchemaType::Discriminator { .. })
+    }
+
+    fn count(schema: &Schema, counts: &mut HashMap<String, usize>) {
+        let in_mapping = is_mapping(schema);
+        for sub in schema.sub_schemas() {
+            if let Some(key) = key(sub).filter(|_| !in_mapping) {
+                *counts.entry(key).or_default() += 1;
+            }
+            count(sub, counts);
+        }
+    }
+
+    // the first replaced schema ends up in `hoisted`
+    fn replace(schema: &mut Schema, target: &str, r#ref: &str, hoisted: &mut Option<Schema>) {
+        let in_mapping = is_mapping(schema);
+        for sub in schema.sub_schemas_mut() {
+            if !in_mapping && key(sub).as_deref() == Some(target) {
+                let nullable = sub.nullable;
+                let schema = std::mem::replace(
+                    sub,
+                    Schema {
+                        ty: SchemaType::Ref {
+                            r#ref: r#ref.to_string(),
+                        },
+                        nullable,
+                        ..Schema::default()
+                    },
+                );
+                hoisted.get_or_insert(Schema {
+                    nullable: false,
+                    ..schema
+                });
+            } else {
+                replace(sub, target, r#ref, hoisted);
+            }
+        }
+    }
+
+    let mut next_id = 1;
+    loop {
+        let mut counts = HashMap::new();
+        count(&root.schema, &mut counts);
+        root.definitions
+            .values()
+            .for_each(|def| count(def, &mut counts));
+
+        let Some(target) = counts
+            .into_iter()
+            .filter(|(_, n)| *n > threshold)
+            .map(|(key, _)| key)
+            .max_by(|l, r| l.len().cmp(&r.len()).then_with(|| r.cmp(l)))
+        else {
+            break;
+        };
+
+        let name = loop {
+            let name = format!("anonymous{}", next_id);
+            next_id += 1;
+            if !root.definitions.contains_key(&name) {
+                break name;
+            }
+        };
+        let r#ref = ref_style.apply(name.clone());
+
+        let mut hoisted = None;
+        replace(&mut root.schema, &target, &r#ref, &mut hoisted);
+        root.definitions
+            .values_mut()
+            .for_each(|def| replace(def, &target, &r#ref, &mut hoisted));
+        root.definitions
+            .insert(name, hoisted.expect("the schema was just counted"));
+    }
+}
+
+/// Order the definitions so that each one comes after the ones it refers to.
+/// Ties are broken alphabetically. In a cycle, the definition reached first
+/// comes last.
+fn topological_order(definitions: &BTreeMap<String, Schema>) -> Vec<String> {
+    fn refs<'a>(
+        schema: &'a Schema,
+        definitions: &'a BTreeMap<String, Schema>,
+        out: &mut BTreeSet<&'a String>,
+    ) {
+        if let SchemaType::Ref { r#ref } = &schema.ty {
+            if let Some((name, _)) = resolve_ref(r#ref, definitions) {
+                out.insert(name);
+            }
+        }
+        schema
+            .sub_schemas()
+            .for_each(|sub| refs(sub, definitions, out));
+    }
+
+    fn visit<'a>(
+        name: &'a String,
+        definitions: &'a BTreeMap<String, Schema>,
+        visited: &mut HashSet<&'a String>,
+        order: &mut Vec<String>,
+    ) {
+        if !visited.insert(name) {
+            return;
+        }
+        let mut deps = BTreeSet::new();
+        refs(&definitions[name], definitions, &mut deps);
+        for dep in deps {
+            visit(dep, definitions, visited, order);
+        }
+        order.push(name.clone());
+    }
+
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    for name in definitions.keys() {
+        visit(name, definitions, &mut visited, &mut order);
+    }
+    order
+}
+
+/// Add a hash of the whole schema to the root metadata. The hash is the 64-bit
+/// FNV-1a of the JSON serialization, which is deterministic since all the maps
+/// involved are ordered.
+fn insert_hash(root: &mut RootSchema) {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let json = serde_json::to_string(root).expect("schemas are always serializable");
+    let hash = json.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+
+    root.schema
+        .metadata
+        .insert("schemaHash", format!("{:016x}", hash).into());
+}
+
+fn int_range(ty: &TypeSchema) -> Option<(i64, i64)> {
+    match ty {
+        TypeSchema::Int8 => Some((i8::MIN.into(), i8::MAX.into())),
+        TypeSchema::Uint8 => Some((u8::MIN.into(), u8::MAX.into())),
+        TypeSchema::Int16 => Some((i16::MIN.into(), i16::MAX.into())),
+        TypeSchema::Uint16 => Some((u16::MIN.into(), u16::MAX.into())),
+        TypeSchema::Int32 => Some((i32::MIN.into(), i32::MAX.into())),
+        TypeSchema::Uint32 => Some((u32::MIN.into(), u32::MAX.into())),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Inlining {
+    Only,
     Always,
     Normal,
     Never,
@@ -222,11 +707,152 @@ impl Default for Inlining {
     }
 }
 
+/// How refs point at definitions.
+///
+/// # Examples
+///
+/// ```
+/// use jtd_derive::{JsonTypedef, Generator, RefStyle};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// let root_schema = Generator::builder()
+///     .top_level_ref()
+///     .naming_short()
+///     .ref_style(RefStyle::Pointer)
+///     .build()
+///     .into_root_schema::<Foo>()
+///     .unwrap();
+/// let json_schema = serde_json::to_value(&root_schema).unwrap();
+///
+/// assert_eq!(json_schema["ref"], "#/definitions/Foo");
+/// assert!(json_schema["definitions"]["Foo"].is_object());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RefStyle {
+    /// The bare definition name, e.g. `"ref": "Foo"`. This is what the
+    /// _Typedef_ spec prescribes and the default.
+    #[default]
+    Name,
+    /// A JSON Pointer fragment to the definition, e.g.
+    /// `"ref": "#/definitions/Foo"`. This isn't valid _Typedef_, but some
+    /// tooling expects it.
+    Pointer,
+}
+
+impl RefStyle {
+    fn apply(self, name: String) -> String {
+        match self {
+            RefStyle::Name => name,
+            RefStyle::Pointer => format!(
+                "#/definitions/{}",
+                name.replace('~', "~0").replace('/', "~1")
+            ),
+        }
+    }
+}
+
+/// The consumer a schema is tailored to, mostly affecting which metadata keys
+/// are used.
+///
+/// # Examples
+///
+/// ```
+/// use jtd_derive::{Dialect, Generator, JsonTypedef};
+///
+/// #[derive(JsonTypedef)]
+/// enum Color {
+///     /// Like the sky.
+///     Blue,
+///     Red,
+/// }
+///
+/// let root_schema = Generator::builder()
+///     .dialect(Dialect::JtdCodegen)
+///     .build()
+///     .into_root_schema::<Color>()
+///     .unwrap();
+/// let json_schema = serde_json::to_value(&root_schema).unwrap();
+///
+/// assert_eq!(json_schema, serde_json::json!{ {
+///     "enum": ["Blue", "Red"],
+///     "metadata": {
+///         "enumDescription": { "Blue": "Like the sky." },
+///     },
+/// } });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dialect {
+    /// Plain _Typedef_. Doc comments only end up in the schema for struct
+    /// variants of enums. This is the default.
+    #[default]
+    Standard,
+    /// Input for [`jtd-codegen`](https://jsontypedef.com/docs/jtd-codegen/).
+    /// Doc comments on types, fields and struct variants go in the
+    /// `description` metadata entry, and the ones on unit variants go in the
+    /// `enumDescription` entry of the enum, keyed by value. That's where
+    /// `jtd-codegen` looks for documentation to put in the generated code.
+    /// If the values are overridden with `#[typedef(enum(...))]`, only the
+    /// variants whose serialized name is one of them get an entry.
+    ///
+    /// `jtd-codegen` rejects anything that isn't valid _Typedef_, so this
+    /// overrides [`RefStyle::Pointer`] and
+    /// [`include_tag_in_variants`](GeneratorBuilder::include_tag_in_variants).
+    JtdCodegen,
+}
+
+/// How integer types that don't fit any _Typedef_ type are handled, i.e. `u64`,
+/// `i64`, `u128`, `i128`, `usize` and `isize`, as well as the seconds of a
+/// `SystemTime`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BigInts {
+    /// Fail generation with [`GenError::Rejected`]. This is the default.
+    #[default]
+    Reject,
+    /// Describe them as `float64`, which is the only _Typedef_ type that
+    /// accepts numbers of their size. That's lossy: a consumer that reads the
+    /// numbers as `float64` only gets integers up to 2^53 exactly. A
+    /// [warning](GeneratorBuilder::collect_warnings) is recorded for each type.
+    Float64,
+}
+
+/// The order of the definitions in a generated [`RootSchema`]. It only affects
+/// serialization; see [`OrderedRootSchema`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DefinitionOrder {
+    /// Sorted by name. This is the default.
+    #[default]
+    Alphabetical,
+    /// Every definition comes after the definitions it refers to. Ties, and
+    /// definitions that refer to each other, are ordered alphabetically as
+    /// far as possible.
+    Topological,
+    /// In the order the types were first encountered during generation, which
+    /// starts with the top-level type and goes depth-first through its fields.
+    Insertion,
+}
+
 /// Builder for [`Generator`]. For example usage, refer to [`Generator`].
 #[derive(Default, Debug)]
 pub struct GeneratorBuilder {
     inlining: Inlining,
     naming_strategy: Option<NamingStrategy>,
+    key_transform: Option<fn(&str) -> String>,
+    deny_additional_properties: bool,
+    int_ranges: bool,
+    nullable_metadata: bool,
+    hash: bool,
+    ref_style: RefStyle,
+    tag_in_variants: bool,
+    warnings: bool,
+    dedup_threshold: Option<usize>,
+    definition_order: DefinitionOrder,
+    strict: bool,
+    dialect: Dialect,
+    big_ints: BigInts,
 }
 
 impl GeneratorBuilder {
@@ -238,6 +864,32 @@ impl GeneratorBuilder {
         self
     }
 
+    /// Inline all types and never emit refs or definitions, e.g. for tools
+    /// that don't support them. Recursive types can't be expressed this way,
+    /// so generating a schema that involves one returns
+    /// [`GenError::RefRequired`].
+    ///
+    /// ```
+    /// use jtd_derive::{JsonTypedef, GenError, Generator};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Recursive {
+    ///     inner: Option<Box<Recursive>>,
+    /// }
+    ///
+    /// let err = Generator::builder()
+    ///     .inline_only()
+    ///     .build()
+    ///     .into_root_schema::<Recursive>()
+    ///     .unwrap_err();
+    ///
+    /// assert!(matches!(err, GenError::RefRequired { .. }));
+    /// ```
+    pub fn inline_only(&mut self) -> &mut Self {
+        self.inlining = Inlining::Only;
+        self
+    }
+
     /// Where possible, provide types by ref even for the top-level type.
     pub fn top_level_ref(&mut self) -> &mut Self {
         self.inlining = Inlining::Never;
@@ -297,11 +949,217 @@ impl GeneratorBuilder {
         self
     }
 
+    /// Transform every definition key produced by the naming strategy, e.g. to
+    /// add a prefix or replace characters an external tool doesn't like. Refs
+    /// are transformed the same way, so they stay resolvable.
+    ///
+    /// ```
+    /// use jtd_derive::{JsonTypedef, Generator};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     x: u32,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .top_level_ref()
+    ///     .naming_short()
+    ///     .definition_key_transform(|key| format!("api.{}", key))
+    ///     .build()
+    ///     .into_root_schema::<Foo>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema["ref"], "api.Foo");
+    /// assert!(json_schema["definitions"]["api.Foo"].is_object());
+    /// ```
+    ///
+    /// Distinct keys that end up identical after the transformation are reported
+    /// as a [`GenError::NameCollision`].
+    pub fn definition_key_transform(&mut self, transform: fn(&str) -> String) -> &mut Self {
+        self.key_transform = Some(transform);
+        self
+    }
+
+    /// Set whether objects allow additional properties when the type doesn't
+    /// say otherwise. The default is `true`, matching how `serde` ignores
+    /// unknown fields.
+    ///
+    /// Setting this to `false` is like putting `#[serde(deny_unknown_fields)]`
+    /// on every type. Types that do have that attribute always deny additional
+    /// properties, regardless of this setting. Denying them leaves the
+    /// `additionalProperties` key out of the schema, rather than setting it to
+    /// `false`.
+    pub fn default_additional_properties(&mut self, allow: bool) -> &mut Self {
+        self.deny_additional_properties = !allow;
+        self
+    }
+
+    /// Add the bounds of integer types to their schemas as `minimum` and
+    /// `maximum` metadata entries. _Typedef_ itself can't constrain numbers
+    /// this way, but documentation generators and some validators can use it.
+    ///
+    /// ```
+    /// use jtd_derive::Generator;
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .emit_int_ranges()
+    ///     .build()
+    ///     .into_root_schema::<u8>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "type": "uint8",
+    ///     "metadata": { "minimum": 0, "maximum": 255 },
+    /// } });
+    /// ```
+    pub fn emit_int_ranges(&mut self) -> &mut Self {
+        self.int_ranges = true;
+        self
+    }
+
+    /// Also mark nullable schemas with a `nullable: true` metadata entry. The
+    /// standard `nullable` flag is still emitted - this is a compatibility shim
+    /// for tools that only look at metadata.
+    ///
+    /// ```
+    /// use jtd_derive::Generator;
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .emit_nullable_metadata()
+    ///     .build()
+    ///     .into_root_schema::<Option<String>>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "type": "string",
+    ///     "nullable": true,
+    ///     "metadata": { "nullable": true },
+    /// } });
+    /// ```
+    pub fn emit_nullable_metadata(&mut self) -> &mut Self {
+        self.nullable_metadata = true;
+        self
+    }
+
+    /// Add a hash of the generated schema to the root metadata as `schemaHash`,
+    /// e.g. for caching or detecting changes. The hash is computed over the
+    /// serialized root schema (including definitions) before it's added, and is
+    /// stable across runs and platforms.
+    ///
+    /// This isn't a cryptographic hash, so don't rely on it to detect tampering.
+    pub fn emit_hash(&mut self) -> &mut Self {
+        self.hash = true;
+        self
+    }
+
+    /// Also declare the tag of discriminator schemas as a string property of
+    /// every variant in the mapping. Some lenient validators expect that.
+    ///
+    /// **This produces invalid _Typedef_ schemas.** The spec forbids mapping
+    /// entries from declaring the discriminator as a property, so strict
+    /// validators and code generators will reject the output.
+    pub fn include_tag_in_variants(&mut self) -> &mut Self {
+        self.tag_in_variants = true;
+        self
+    }
+
+    /// Collect warnings about things schemas can't capture, like the uniqueness
+    /// of set elements or what variants an untagged enum has. They're available
+    /// through [`Generator::warnings`] and
+    /// [`Generator::into_root_schema_with_warnings`].
+    pub fn collect_warnings(&mut self) -> &mut Self {
+        self.warnings = true;
+        self
+    }
+
+    /// Move object schemas that occur more than `threshold` times without a
+    /// definition of their own into definitions called `anonymous1`,
+    /// `anonymous2` and so on, and refer to them instead.
+    ///
+    /// Only schemas in the `properties` and `discriminator` forms are considered.
+    /// The variants in a discriminator's `mapping` stay inline, since JSON Typedef
+    /// doesn't allow refs there, though schemas nested in them can be hoisted.
+    /// Nothing is deduplicated if refs are forbidden with
+    /// [`inline_only`](GeneratorBuilder::inline_only).
+    pub fn dedup_anonymous(&mut self, threshold: usize) -> &mut Self {
+        self.dedup_threshold = Some(threshold);
+        self
+    }
+
+    /// Set the order of the definitions in the schema produced by
+    /// [`Generator::into_ordered_root_schema`]. The default is
+    /// [`DefinitionOrder::Alphabetical`].
+    ///
+    /// `serde_json::Value` sorts object keys on its own, so the order only shows
+    /// when the [`OrderedRootSchema`] is serialized directly, e.g. with
+    /// `serde_json::to_string`.
+    pub fn definition_order(&mut self, order: DefinitionOrder) -> &mut Self {
+        self.definition_order = order;
+        self
+    }
+
+    /// Check the generated schema against the rules of the _Typedef_ spec with
+    /// [`RootSchema::validate`] and fail with [`GenError::InvalidSchema`] if
+    /// it breaks any. Derived schemas are always valid, but hand-written
+    /// [`JsonTypedef`] impls and `schema_with` functions might not be.
+    ///
+    /// Combining this with
+    /// [`include_tag_in_variants`](GeneratorBuilder::include_tag_in_variants)
+    /// makes generating any tagged enum fail.
+    pub fn strict(&mut self) -> &mut Self {
+        self.strict = true;
+        self
+    }
+
+    /// Tailor the schema to some consumer. The default is [`Dialect::Standard`].
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Set how integers too big for any _Typedef_ type are handled. The default
+    /// is [`BigInts::Reject`].
+    pub fn big_ints(&mut self, big_ints: BigInts) -> &mut Self {
+        self.big_ints = big_ints;
+        self
+    }
+
+    /// Set how refs point at definitions. The default is [`RefStyle::Name`].
+    pub fn ref_style(&mut self, style: RefStyle) -> &mut Self {
+        self.ref_style = style;
+        self
+    }
+
     /// Finalize the configuration and get a `Generator`.
     pub fn build(&mut self) -> Generator {
         Generator {
             inlining: self.inlining,
-            naming_strategy: self.naming_strategy.take().unwrap_or_default(),
+            deny_additional_properties: self.deny_additional_properties,
+            int_ranges: self.int_ranges,
+            nullable_metadata: self.nullable_metadata,
+            hash: self.hash,
+            ref_style: match self.dialect {
+                Dialect::Standard => self.ref_style,
+                Dialect::JtdCodegen => RefStyle::Name,
+            },
+            tag_in_variants: self.tag_in_variants && self.dialect == Dialect::Standard,
+            warnings: self.warnings.then(Vec::new),
+            dedup_threshold: self.dedup_threshold,
+            definition_order: self.definition_order,
+            strict: self.strict,
+            dialect: self.dialect,
+            big_ints: self.big_ints,
+            naming_strategy: match self.key_transform {
+                Some(transform) => self
+                    .naming_strategy
+                    .take()
+                    .unwrap_or_default()
+                    .then(transform),
+                None => self.naming_strategy.take().unwrap_or_default(),
+            },
             ..Generator::default()
         }
     }
@@ -347,4 +1205,16 @@ pub enum GenError {
         type2: String,
         id: String,
     },
+    /// A type could only be expressed using a ref, but refs were forbidden with
+    /// [`GeneratorBuilder::inline_only`]. This happens with recursive types.
+    #[error("type `{type_name}` is recursive, so it can't be inlined")]
+    RefRequired { type_name: String },
+    /// The generated schema breaks the _Typedef_ spec. Only checked when
+    /// enabled with [`GeneratorBuilder::strict`].
+    #[error("the generated schema is invalid: {0}")]
+    InvalidSchema(#[from] ValidationError),
+    /// The schema of a type was rejected, e.g. by a
+    /// `#[typedef(validate_with = "...")]` function.
+    #[error("the schema of `{type_name}` was rejected: {message}")]
+    Rejected { type_name: String, message: String },
 }
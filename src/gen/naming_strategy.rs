@@ -46,6 +46,11 @@ impl NamingStrategy {
         Self(Box::new(fun))
     }
 
+    /// Apply `transform` to every name this strategy produces.
+    pub fn then(self, transform: fn(&str) -> String) -> Self {
+        Self(Box::new(move |names| transform(&self.0(names))))
+    }
+
     pub fn fun(&self) -> &dyn Fn(&Names) -> String {
         &self.0
     }
@@ -59,13 +64,9 @@ impl Default for NamingStrategy {
 
 impl std::fmt::Debug for NamingStrategy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let example = Names {
-            short: "Foo",
-            long: "my_crate::Foo",
-            nullable: false,
-            type_params: vec![u32::names()],
-            const_params: vec!["5".to_string()],
-        };
+        let example = Names::new("Foo", "my_crate::Foo")
+            .with_type_params([u32::names()])
+            .with_const_params([5]);
         let result = self.fun()(&example);
 
         f.write_fmt(format_args!(
@@ -31,13 +31,235 @@
 //!     "additionalProperties": true,
 //! } });
 //! ```
+//!
+//! # Attributes
+//!
+//! The derive macro understands the `serde` attributes that affect the shape of
+//! the JSON, so the schema matches how the type is serialized. Types that don't
+//! derive `serde` traits can use the equivalent `#[typedef(...)]` attributes.
+//! Where `serde` allows different names for serialization and deserialization,
+//! the deserialization one is used.
+//!
+//! Container attributes:
+//!
+//! - `#[typedef(tag = "...")]` - use the internally tagged enum representation.
+//! - `#[typedef(untagged)]` - use the untagged enum representation. See
+//!   [below](#untagged-enums).
+//! - `#[typedef(deny_unknown_fields)]` - don't allow additional properties.
+//! - `#[typedef(transparent)]` - represent a single-field struct as its field.
+//! - `#[typedef(transparent_elements = "...")]` - represent a newtype around some
+//!   collection as an array of the given item type. The wrapped type doesn't need
+//!   to implement [`JsonTypedef`], so this works for collections from other crates.
+//! - `#[typedef(as_values)]` - represent a struct whose fields all have the same
+//!   type as a map of that type, i.e. a `values` schema. The field names and
+//!   which of them are required are lost. `optional` fields count as the type
+//!   inside the `Option`, and skipped fields are ignored.
+//! - `#[typedef(map_key)]` - implement [`JsonTypedefMapKey`], so the type can be
+//!   the key of a map. Only use this for types that are serialized as strings. See
+//!   [below](#map-keys).
+//! - `#[typedef(from = "...")]`, `#[typedef(try_from = "...")]` - use the schema
+//!   of another type.
+//! - `#[typedef(bound = "...")]` - add `where` predicates to the generated impl.
+//!   See [below](#generic-types).
+//! - `#[typedef(rename_all = "...")]` - rename all fields or variants. A `rename`
+//!   on a field or variant takes precedence. The properties merged in by a
+//!   flattened field aren't affected; they're named by the flattened type. Two
+//!   fields that end up with the same name are a compile error.
+//! - `#[typedef(default)]` - make all fields optional.
+//! - `#[typedef(metadata(key = "json", ...))]` - add metadata to the schema.
+//!   The values are parsed as JSON.
+//! - `#[typedef(metadata_from = "path::to::fn")]` - add metadata computed at runtime.
+//!   The function must return something like `serde_json::Map<String, Value>`.
+//!   Its entries are merged in after the ones from `metadata(...)`.
+//! - `#[typedef(validate_with = "path::to::fn")]` - check the type's schema with
+//!   a function like `fn(&Schema) -> Result<(), String>`, e.g. to enforce some
+//!   convention. It runs once the schema is complete, metadata included. An `Err`
+//!   makes generation fail with [`GenError::Rejected`].
+//! - `#[typedef(enum("a", "b-c", ...))]` - on enums with unit variants, use the
+//!   given values instead of the ones derived from the variants. Useful if the JSON
+//!   values aren't valid Rust identifiers.
+//! - `#[typedef(single_variant_as_properties)]` - on a tagged enum with exactly one
+//!   variant, generate a `properties` schema with the tag as a property with a
+//!   single-value `enum`, rather than a `discriminator` with one entry. Both
+//!   describe the same JSON. The `properties` form is simpler for consumers,
+//!   while the `discriminator` form makes it painless to add variants later.
+//! - `#[typedef(repr = "uint8")]` - on enums with unit variants serialized as
+//!   their discriminants (e.g. with `serde_repr`), generate a schema of the given
+//!   integer type. The discriminants are listed in the `values` metadata entry.
+//!   They have to be integer literals, if given explicitly.
+//!
+//! Variant attributes:
+//!
+//! - `#[typedef(rename = "...")]` - use the given name for this variant. The
+//!   name is used verbatim and can contain any characters.
+//! - `#[typedef(rename_all = "...")]` - rename all fields of this struct variant.
+//!   On an enum, the container attribute renames the variants instead.
+//! - `#[typedef(tag_value = "...")]` - set the value of the tag identifying this
+//!   variant, i.e. its key in the discriminator `mapping` or its entry in the tag's
+//!   `enum`. The value is used verbatim and `rename_all` doesn't apply to it.
+//!   Unlike a rename, it only ever affects the tag, so it requires the enum to be
+//!   tagged.
+//! - `#[typedef(null_variant)]` - represent this unit variant as `null` rather
+//!   than listing it among the other variants. The enum's schema becomes nullable.
+//!   Only one variant can have this attribute. This doesn't change how `serde`
+//!   serializes the variant, so it's meant for types with custom serialization.
+//!
+//! Doc comments on struct variants end up in the `description` metadata entry of
+//! the variant's schema in the discriminator `mapping`. With
+//! [`Dialect::JtdCodegen`], doc comments on types, fields and unit variants are
+//! used too.
+//!
+//! An `Option` of a tagged enum gets the enum's discriminator schema with
+//! `nullable` set. That's valid _Typedef_, but some code generators handle
+//! nullable discriminators poorly.
+//!
+//! Field attributes:
+//!
+//! - `#[typedef(skip)]` - leave the field out of the schema. Fields with
+//!   `#[serde(skip)]` or both `skip_serializing` and `skip_deserializing` are left
+//!   out too. A skipped field's type doesn't need to implement [`JsonTypedef`].
+//! - `#[typedef(rename = "...")]` - use the given property name for this field.
+//!   The name is used verbatim and can contain any characters.
+//! - `#[typedef(metadata(key = "json", ...))]` - add metadata to the field's schema.
+//! - `#[typedef(group = "...")]` - add a `group` metadata entry to the field's schema,
+//!   e.g. for form generators that render related fields together.
+//! - `#[typedef(optional)]` - put the field in `optionalProperties`. The field
+//!   has to be an `Option`, possibly wrapped in `Box`, `Rc` or `Arc`. That
+//!   outermost `Option` is represented by the field being optional, so e.g.
+//!   `Option<u32>` becomes a non-nullable `uint32`, while `Option<Box<Option<u32>>>`
+//!   becomes a nullable one. Without this attribute, an `Option` field is a
+//!   required, nullable property. The `Option` has to be spelled out, since
+//!   the macro can't see through type aliases.
+//! - `#[typedef(flatten)]` - merge the properties of the field's type into this
+//!   struct. The type has to have a schema in the properties form, e.g. be
+//!   a struct with named fields. If the field is an `Option`, all the merged
+//!   properties become optional. Flattening a map, e.g. a `HashMap<String, V>`
+//!   collecting unknown fields, allows additional properties instead. The type of
+//!   the map's values is lost, since _Typedef_ can't describe those properties.
+//!   Flattening anything else, like an internally tagged enum, makes generation
+//!   fail with [`GenError::Rejected`].
+//! - `#[typedef(type = "...")]` - use the schema of another type for this field.
+//! - `#[typedef(schema_with = "path::to::fn")]` - use the schema returned by
+//!   a function with the signature `fn(&mut Generator) -> Schema`.
+//!
+//! Fields using `#[serde(with = "...")]`, `#[serde(serialize_with = "...")]` or
+//! `#[serde(deserialize_with = "...")]` can be serialized in any way, so the derive
+//! macro refuses to guess their schema. One of the last two attributes is required
+//! for them.
+//!
+//! # Generic types
+//!
+//! The derive macro requires every type parameter to implement [`JsonTypedef`],
+//! even if no field uses it, e.g. when it only appears in a skipped `PhantomData`.
+//! That's because the parameters are part of the type's [`names`](JsonTypedef::names),
+//! which keeps `Id<User>` and `Id<Order>` apart as separate definitions. A type
+//! parameter that doesn't implement [`JsonTypedef`] would leave nothing to name
+//! it by.
+//!
+//! ```
+//! use std::marker::PhantomData;
+//! use jtd_derive::JsonTypedef;
+//!
+//! #[derive(JsonTypedef)]
+//! struct Id<T> {
+//!     id: u32,
+//!     #[typedef(skip)]
+//!     marker: PhantomData<T>,
+//! }
+//! ```
+//!
+//! Those bounds don't cover fields of types like associated types, which the macro
+//! can't reason about. Extra `where` predicates can be added with the
+//! `#[typedef(bound = "...")]` container attribute:
+//!
+//! ```
+//! use jtd_derive::JsonTypedef;
+//!
+//! trait Kind {
+//!     type Output;
+//! }
+//!
+//! #[derive(JsonTypedef)]
+//! #[typedef(bound = "<T as Kind>::Output: JsonTypedef")]
+//! struct Wrapper<T: Kind> {
+//!     value: <T as Kind>::Output,
+//! }
+//! ```
+//!
+//! # Map keys
+//!
+//! Maps like `HashMap<K, V>` have a `values` schema, which only describes JSON
+//! objects, so `K` has to implement [`JsonTypedefMapKey`]. Strings, `char`s,
+//! integers and derived enums with only unit variants serialized as strings
+//! already do. **Earlier versions accepted any `K: ToString`**; other key types,
+//! e.g. newtypes with a `Display`-based `Serialize` impl, now need the
+//! `#[typedef(map_key)]` attribute or a manual `impl JsonTypedefMapKey for K {}`.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use jtd_derive::JsonTypedef;
+//!
+//! #[derive(JsonTypedef)]
+//! #[typedef(map_key)]
+//! struct UserId(String);
+//!
+//! #[derive(JsonTypedef)]
+//! struct Scores {
+//!     by_user: HashMap<UserId, u32>,
+//! }
+//! ```
+//!
+//! # Untagged enums
+//!
+//! **_Typedef_ can't express untagged unions.** Enums using the untagged
+//! representation are only accepted if every variant has exactly one unnamed field,
+//! e.g. a `StringOrNumber` enum. Their schema is the empty schema, which accepts
+//! **any** JSON value. The schemas of the variants are listed in the `untagged`
+//! metadata entry, so that humans and tools at least know what to expect:
+//!
+//! ```
+//! use jtd_derive::{JsonTypedef, Generator};
+//!
+//! #[derive(JsonTypedef)]
+//! #[typedef(untagged)]
+//! enum StringOrNumber {
+//!     String(String),
+//!     Number(u32),
+//! }
+//!
+//! let root_schema = Generator::default().into_root_schema::<StringOrNumber>().unwrap();
+//! let json_schema = serde_json::to_value(&root_schema).unwrap();
+//!
+//! assert_eq!(json_schema, serde_json::json!{ {
+//!     "metadata": {
+//!         "untagged": [{ "type": "string" }, { "type": "uint32" }],
+//!     },
+//! } });
+//! ```
+//!
+//! Validators won't reject anything here, so prefer a tagged representation when
+//! you control the format.
+//!
+//! # Features
+//!
+//! - `bytes` - implement [`JsonTypedef`] for `bytes::Bytes` and `bytes::BytesMut`.
+//!   Their schema is that of `Vec<u8>`, an array of `uint8`, since that's how
+//!   they're serialized by default. If yours are serialized as base64 strings
+//!   instead, use `#[typedef(type = "String")]` on those fields.
 
+pub mod build;
 mod gen;
 mod names;
+mod registry;
 pub mod schema;
+mod snapshot;
 mod r#trait;
 mod type_id;
 
-pub use gen::{GenError, Generator};
+pub use gen::{BigInts, DefinitionOrder, Dialect, GenError, Generator, RefStyle};
 pub use names::Names;
-pub use r#trait::JsonTypedef;
+pub use r#trait::{JsonTypedef, JsonTypedefMapKey};
+pub use registry::SchemaRegistry;
+#[doc(hidden)]
+pub use snapshot::__assert_schema_matches;
+pub use snapshot::OVERWRITE_ENV_VAR;
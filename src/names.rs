@@ -17,3 +17,54 @@ pub struct Names {
     /// The values of constant arguments represented as strings.
     pub const_params: Vec<String>,
 }
+
+impl Names {
+    /// Names without any type or const params.
+    ///
+    /// ```
+    /// use jtd_derive::Names;
+    ///
+    /// let names = Names::new("Foo", "my_crate::Foo")
+    ///     .with_type_params([Names::new("uint32", "uint32")])
+    ///     .with_const_params([5]);
+    ///
+    /// assert_eq!(names.short, "Foo");
+    /// assert_eq!(names.type_params[0].long, "uint32");
+    /// assert_eq!(names.const_params, ["5"]);
+    /// ```
+    pub fn new(short: &'static str, long: &'static str) -> Self {
+        Self {
+            short,
+            long,
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+
+    /// Names for a type that's never referenced by name, i.e. whose
+    /// [`referenceable`](crate::JsonTypedef::referenceable) returns `false`.
+    /// If such a type is used as a type argument of a referenceable one,
+    /// it'll show up as `anonymous` in that type's name.
+    pub fn anonymous() -> Self {
+        Self::new("anonymous", "anonymous")
+    }
+
+    /// Set the names of the type arguments.
+    pub fn with_type_params(mut self, type_params: impl IntoIterator<Item = Names>) -> Self {
+        self.type_params = type_params.into_iter().collect();
+        self
+    }
+
+    /// Set the values of the const arguments.
+    pub fn with_const_params(
+        mut self,
+        const_params: impl IntoIterator<Item = impl ToString>,
+    ) -> Self {
+        self.const_params = const_params
+            .into_iter()
+            .map(|param| param.to_string())
+            .collect();
+        self
+    }
+}
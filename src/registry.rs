@@ -0,0 +1,142 @@
+//! A registry of schemas to be bundled into one document.
+
+use std::collections::BTreeMap;
+
+use crate::schema::{OrderedRootSchema, RootSchema, Schema};
+use crate::{GenError, Generator, JsonTypedef};
+
+/// A collection of named schema generators. This is useful when the set of types
+/// to generate schemas for is only known at runtime, e.g. in plugin systems.
+///
+/// Types are usually added using the [`register_schema!`](crate::register_schema)
+/// macro. Registering a name twice replaces the earlier entry.
+///
+/// A registered type gets the same definition name as it would get if some other
+/// type referred to it, so registering a type alongside the types that use it
+/// produces one definition, and refs to it resolve.
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::{register_schema, Generator, JsonTypedef, SchemaRegistry};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// let mut registry = SchemaRegistry::new();
+/// register_schema!(registry, Foo);
+///
+/// let root_schema = registry
+///     .into_bundle(Generator::builder().naming_short().build())
+///     .unwrap();
+/// let json_schema = serde_json::to_value(&root_schema).unwrap();
+///
+/// assert_eq!(json_schema, serde_json::json!{ {
+///     "definitions": {
+///         "Foo": {
+///             "properties": {
+///                 "x": { "type": "uint32" }
+///             },
+///             "additionalProperties": true,
+///         }
+///     },
+/// } });
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct SchemaRegistry {
+    entries: BTreeMap<&'static str, Entry>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Entry {
+    /// A schema defined under a fixed name.
+    Named(fn(&mut Generator) -> Schema),
+    /// A type defined under whatever name the generator gives it. Returns the
+    /// schema instead if the type can't be referenced, in which case it's
+    /// defined under the registered name.
+    Type(fn(&mut Generator) -> Option<Schema>),
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a schema under the given name.
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        schema: fn(&mut Generator) -> Schema,
+    ) -> &mut Self {
+        self.entries.insert(name, Entry::Named(schema));
+        self
+    }
+
+    /// Register a type. Its definition is named by the generator's
+    /// [naming strategy](crate::gen::GeneratorBuilder::naming_short), just like
+    /// the definitions of the types it refers to. Only types that can't be
+    /// referenced, e.g. `Vec<Foo>`, are defined under `name` instead.
+    ///
+    /// `name` is also what identifies the entry in this registry, e.g. in
+    /// [`SchemaRegistry::names`].
+    pub fn register_type<T: JsonTypedef>(&mut self, name: &'static str) -> &mut Self {
+        self.entries
+            .insert(name, Entry::Type(Generator::define::<T>));
+        self
+    }
+
+    /// Returns the registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.keys().copied()
+    }
+
+    /// Generate a root schema containing every registered schema as a definition,
+    /// alongside any definitions those require. The top-level schema is empty.
+    ///
+    /// This will return an error if a naming collision is detected, including
+    /// a registered name colliding with a definition generated for some type.
+    ///
+    /// The bundle goes through the same post-processing as the output of
+    /// [`Generator::into_root_schema`], e.g.
+    /// [deduplication](crate::gen::GeneratorBuilder::dedup_anonymous).
+    pub fn into_bundle(self, generator: Generator) -> Result<RootSchema, GenError> {
+        self.into_ordered_bundle(generator)
+            .map(OrderedRootSchema::into_root)
+    }
+
+    /// Like [`SchemaRegistry::into_bundle`], but keeps the order of the
+    /// definitions set with
+    /// [`GeneratorBuilder::definition_order`](crate::gen::GeneratorBuilder::definition_order).
+    /// Registered names that aren't type definitions come last. See
+    /// [`Generator::into_ordered_root_schema`].
+    pub fn into_ordered_bundle(self, generator: Generator) -> Result<OrderedRootSchema, GenError> {
+        generator.into_bundle(self.entries)
+    }
+}
+
+/// Register one or more types with a [`SchemaRegistry`] using
+/// [`SchemaRegistry::register_type`]. The name each type is registered under is
+/// its name as written in the macro invocation.
+///
+/// ```
+/// # use jtd_derive::{register_schema, JsonTypedef, SchemaRegistry};
+/// # #[derive(JsonTypedef)]
+/// # struct Foo { x: u32 }
+/// # #[derive(JsonTypedef)]
+/// # struct Bar { y: String }
+/// let mut registry = SchemaRegistry::new();
+/// register_schema!(registry, Foo, Bar);
+///
+/// assert_eq!(registry.names().collect::<Vec<_>>(), ["Bar", "Foo"]);
+/// ```
+#[macro_export]
+macro_rules! register_schema {
+    ($registry:expr, $($ty:ty),+ $(,)?) => {
+        $(
+            $registry.register_type::<$ty>(stringify!($ty));
+        )+
+    };
+}
@@ -1,7 +1,7 @@
 //! The internal Rust representation of a [_JSON Typedef_](https://jsontypedef.com/)
 //! schema.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use serde::Serialize;
 
@@ -10,7 +10,7 @@ use serde::Serialize;
 // serde derives makes this so very easy. Damnit.
 
 /// The top level of a [_JSON Typedef_](https://jsontypedef.com/) schema.
-#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
 pub struct RootSchema {
     /// The top-level
     /// [definitions](https://jsontypedef.com/docs/jtd-in-5-minutes/#ref-schemas).
@@ -21,6 +21,130 @@ pub struct RootSchema {
     pub schema: Schema,
 }
 
+/// A [`RootSchema`] along with the order its definitions are serialized in, as
+/// produced by [`Generator::into_ordered_root_schema`](crate::Generator::into_ordered_root_schema).
+/// See [`DefinitionOrder`](crate::DefinitionOrder).
+///
+/// Serializing this gives the same JSON as serializing the root schema, except
+/// for the order of the `definitions`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OrderedRootSchema {
+    root: RootSchema,
+    order: Vec<String>,
+}
+
+impl OrderedRootSchema {
+    /// `order` doesn't have to list every definition. The ones it leaves out
+    /// come last, in alphabetical order.
+    pub(crate) fn new(root: RootSchema, order: Vec<String>) -> Self {
+        Self { root, order }
+    }
+
+    /// The root schema itself.
+    pub fn root(&self) -> &RootSchema {
+        &self.root
+    }
+
+    /// Discard the order and return the root schema.
+    pub fn into_root(self) -> RootSchema {
+        self.root
+    }
+
+    /// The keys of the definitions in the order they're serialized in.
+    pub fn definition_order(&self) -> impl Iterator<Item = &str> {
+        let mut done = HashSet::new();
+        self.order
+            .iter()
+            .filter(|key| self.root.definitions.contains_key(*key))
+            .chain(self.root.definitions.keys())
+            .filter(move |key| done.insert(*key))
+            .map(String::as_str)
+    }
+
+    /// Like [`RootSchema::to_pretty_canonical_string`], but with the definitions
+    /// in this order rather than sorted.
+    pub fn to_pretty_canonical_string(&self) -> String {
+        let definitions = &self.root.definitions;
+        self.root.to_canonical(
+            self.definition_order()
+                .map(|key| (key.to_string(), Canonical::from_schema(&definitions[key])))
+                .collect(),
+        )
+    }
+}
+
+impl Serialize for OrderedRootSchema {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Repr<'a> {
+            #[serde(skip_serializing_if = "Definitions::is_empty")]
+            definitions: Definitions<'a>,
+            #[serde(flatten)]
+            schema: &'a Schema,
+        }
+
+        struct Definitions<'a>(&'a OrderedRootSchema);
+
+        impl Definitions<'_> {
+            fn is_empty(&self) -> bool {
+                self.0.root.definitions.is_empty()
+            }
+        }
+
+        impl Serialize for Definitions<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let definitions = &self.0.root.definitions;
+                serializer.collect_map(
+                    self.0
+                        .definition_order()
+                        .map(|key| (key, &definitions[key])),
+                )
+            }
+        }
+
+        Repr {
+            definitions: Definitions(self),
+            schema: &self.root.schema,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl RootSchema {
+    /// Returns a copy of this schema with all metadata removed, including
+    /// from the definitions. See [`Schema::strip_metadata`].
+    pub fn strip_metadata(&self) -> RootSchema {
+        RootSchema {
+            definitions: self
+                .definitions
+                .iter()
+                .map(|(key, schema)| (key.clone(), schema.strip_metadata()))
+                .collect(),
+            schema: self.schema.strip_metadata(),
+        }
+    }
+
+    /// The number of schema nodes in the top-level schema and all definitions.
+    /// See [`Schema::count_nodes`].
+    pub fn count_nodes(&self) -> usize {
+        self.schema.count_nodes()
+            + self
+                .definitions
+                .values()
+                .map(Schema::count_nodes)
+                .sum::<usize>()
+    }
+
+    /// The size in bytes of this schema serialized as compact JSON. Together with
+    /// [`RootSchema::count_nodes`], this is a way to compare the output of
+    /// different [`Generator`](crate::Generator) settings.
+    pub fn serialized_size(&self) -> usize {
+        serde_json::to_vec(self)
+            .expect("schemas are always serializable")
+            .len()
+    }
+}
+
 /// A [_JSON Typedef_](https://jsontypedef.com/) schema.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub struct Schema {
@@ -35,6 +159,204 @@ pub struct Schema {
     pub nullable: bool,
 }
 
+impl Schema {
+    /// Returns the immediate child schemas of this schema, i.e. property values,
+    /// elements, values or discriminator mapping entries.
+    ///
+    /// This does not resolve refs and does not recurse.
+    pub fn sub_schemas(&self) -> impl Iterator<Item = &Schema> {
+        let children: Vec<_> = match &self.ty {
+            SchemaType::Empty
+            | SchemaType::Type { .. }
+            | SchemaType::Enum { .. }
+            | SchemaType::Ref { .. } => vec![],
+            SchemaType::Elements { elements } => vec![elements.as_ref()],
+            SchemaType::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => properties
+                .values()
+                .chain(optional_properties.values())
+                .collect(),
+            SchemaType::Values { values } => vec![values.as_ref()],
+            SchemaType::Discriminator { mapping, .. } => mapping.values().collect(),
+        };
+
+        children.into_iter()
+    }
+
+    /// Like [`Schema::sub_schemas`], but provides mutable references. Combined with
+    /// recursion, this can be used to transform a schema in place.
+    pub fn sub_schemas_mut(&mut self) -> impl Iterator<Item = &mut Schema> {
+        let children: Vec<_> = match &mut self.ty {
+            SchemaType::Empty
+            | SchemaType::Type { .. }
+            | SchemaType::Enum { .. }
+            | SchemaType::Ref { .. } => vec![],
+            SchemaType::Elements { elements } => vec![elements.as_mut()],
+            SchemaType::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => properties
+                .values_mut()
+                .chain(optional_properties.values_mut())
+                .collect(),
+            SchemaType::Values { values } => vec![values.as_mut()],
+            SchemaType::Discriminator { mapping, .. } => mapping.values_mut().collect(),
+        };
+
+        children.into_iter()
+    }
+}
+
+impl Schema {
+    /// Returns a copy of this schema with the metadata of it and all of its
+    /// sub-schemas removed. Metadata doesn't affect validation, so this is
+    /// handy for producing a lean schema to validate with.
+    pub fn strip_metadata(&self) -> Schema {
+        fn strip(schema: &mut Schema) {
+            schema.metadata = Metadata::default();
+            schema.sub_schemas_mut().for_each(strip);
+        }
+
+        let mut schema = self.clone();
+        strip(&mut schema);
+        schema
+    }
+
+    /// The number of schema nodes in this schema, i.e. this one plus all of its
+    /// sub-schemas, recursively. Refs count as one node and aren't followed.
+    ///
+    /// ```
+    /// use jtd_derive::Generator;
+    ///
+    /// let root_schema = Generator::default()
+    ///     .into_root_schema::<Vec<Option<u32>>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(root_schema.schema.count_nodes(), 2);
+    /// ```
+    pub fn count_nodes(&self) -> usize {
+        1 + self.sub_schemas().map(Schema::count_nodes).sum::<usize>()
+    }
+}
+
+impl Schema {
+    /// Returns a copy of this schema with every ref to one of the `definitions`
+    /// replaced by the definition itself, so that schemas can be compared no
+    /// matter how they were split into definitions, e.g. when generated with
+    /// different inlining settings. Refs to definitions that are part of a cycle
+    /// are kept as they are, since they can't be inlined. Refs to unknown
+    /// definitions are kept too.
+    ///
+    /// The nullability and metadata of a ref are merged into the inlined schema.
+    pub fn canonicalize(&self, definitions: &BTreeMap<String, Schema>) -> Schema {
+        // whether `schema` refers back to `target`, directly or through other definitions
+        fn reaches<'a>(
+            schema: &'a Schema,
+            target: &str,
+            definitions: &'a BTreeMap<String, Schema>,
+            visited: &mut HashSet<&'a str>,
+        ) -> bool {
+            if let SchemaType::Ref { r#ref } = &schema.ty {
+                if let Some((name, def)) = resolve_ref(r#ref, definitions) {
+                    if name == target {
+                        return true;
+                    }
+                    if visited.insert(name) && reaches(def, target, definitions, visited) {
+                        return true;
+                    }
+                }
+            }
+            schema
+                .sub_schemas()
+                .any(|sub| reaches(sub, target, definitions, visited))
+        }
+
+        fn inline(
+            schema: &mut Schema,
+            definitions: &BTreeMap<String, Schema>,
+            cyclic: &mut HashMap<String, bool>,
+        ) {
+            if let SchemaType::Ref { r#ref } = &schema.ty {
+                if let Some((name, def)) = resolve_ref(r#ref, definitions) {
+                    let is_cyclic = *cyclic
+                        .entry(name.clone())
+                        .or_insert_with(|| reaches(def, name, definitions, &mut HashSet::new()));
+
+                    if !is_cyclic {
+                        let mut metadata = def.metadata.clone();
+                        metadata.extend(std::mem::take(&mut schema.metadata).0);
+                        *schema = Schema {
+                            metadata,
+                            ty: def.ty.clone(),
+                            nullable: def.nullable || schema.nullable,
+                        };
+                    }
+                }
+            }
+
+            for sub in schema.sub_schemas_mut() {
+                inline(sub, definitions, cyclic);
+            }
+        }
+
+        let mut schema = self.clone();
+        inline(&mut schema, definitions, &mut HashMap::new());
+        schema
+    }
+}
+
+impl Schema {
+    /// Start building a discriminator schema with the given tag. Variants are
+    /// added with [`DiscriminatorBuilder::variant`] and checked against the spec
+    /// by [`DiscriminatorBuilder::build`].
+    ///
+    /// ```
+    /// use jtd_derive::schema::{Schema, SchemaType, TypeSchema};
+    ///
+    /// let x = Schema {
+    ///     ty: SchemaType::Type {
+    ///         r#type: TypeSchema::Uint32,
+    ///     },
+    ///     ..Schema::default()
+    /// };
+    /// let props = Schema {
+    ///     ty: SchemaType::Properties {
+    ///         properties: [("x", x)].into(),
+    ///         optional_properties: [].into(),
+    ///         additional_properties: false,
+    ///     },
+    ///     ..Schema::default()
+    /// };
+    ///
+    /// let schema = Schema::discriminator("type")
+    ///     .variant("A", props.clone())
+    ///     .variant("B", props)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     serde_json::to_value(&schema).unwrap(),
+    ///     serde_json::json!({
+    ///         "discriminator": "type",
+    ///         "mapping": {
+    ///             "A": { "properties": { "x": { "type": "uint32" } } },
+    ///             "B": { "properties": { "x": { "type": "uint32" } } },
+    ///         },
+    ///     })
+    /// );
+    /// ```
+    pub fn discriminator(tag: &'static str) -> DiscriminatorBuilder {
+        DiscriminatorBuilder {
+            tag,
+            variants: vec![],
+        }
+    }
+}
+
 impl Default for Schema {
     /// Provides an [empty schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#empty-schemas).
     /// Empty schemas accept any JSON data.
@@ -52,16 +374,18 @@ impl Default for Schema {
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 #[serde(untagged)]
 pub enum SchemaType {
+    /// The empty form, serialized as `{}`. Accepts any JSON value.
     Empty,
-    Type {
-        r#type: TypeSchema,
-    },
-    Enum {
-        r#enum: Vec<&'static str>,
-    },
-    Elements {
-        elements: Box<Schema>,
-    },
+    /// The type form, describing a primitive value.
+    Type { r#type: TypeSchema },
+    /// The enum form, describing a string with one of the given values.
+    Enum { r#enum: Vec<&'static str> },
+    /// The elements form, describing an array.
+    Elements { elements: Box<Schema> },
+    /// The properties form, describing an object with known keys.
+    ///
+    /// `additionalProperties` is only serialized when it's `true`. `false` is what
+    /// _Typedef_ assumes if the key is missing, so it's never emitted explicitly.
     #[serde(rename_all = "camelCase")]
     Properties {
         #[serde(skip_serializing_if = "BTreeMap::is_empty")]
@@ -71,17 +395,16 @@ pub enum SchemaType {
         #[serde(skip_serializing_if = "std::ops::Not::not")]
         additional_properties: bool,
     },
-    Values {
-        values: Box<Schema>,
-    },
+    /// The values form, describing an object used as a map.
+    Values { values: Box<Schema> },
+    /// The discriminator form, describing a tagged union of objects.
     Discriminator {
         discriminator: &'static str,
         // Can only contain non-nullable "properties" schemas
         mapping: BTreeMap<&'static str, Schema>,
     },
-    Ref {
-        r#ref: String,
-    },
+    /// The ref form, referring to one of the top-level definitions.
+    Ref { r#ref: String },
 }
 
 /// Typedef primitive types. See [the Typedef docs entry](https://jsontypedef.com/docs/jtd-in-5-minutes/#type-schemas).
@@ -124,27 +447,490 @@ impl TypeSchema {
 /// Metadata is a freeform map and a way to extend Typedef. The spec doesn't specify
 /// what might go in there. By default, `jtd_derive` doesn't generate any metadata.
 #[derive(Default, Debug, PartialEq, Eq, Clone, Serialize)]
-pub struct Metadata(BTreeMap<&'static str, serde_json::Value>);
+pub struct Metadata(BTreeMap<String, serde_json::Value>);
 
 impl Metadata {
-    /// Construct a [`Metadata`] object from something that can be converted
-    /// to the appropriate hashmap.
-    pub fn from_map(m: impl Into<BTreeMap<&'static str, serde_json::Value>>) -> Self {
-        Self(m.into())
+    /// Construct a [`Metadata`] object from key-value pairs, e.g. a map or
+    /// an array of tuples.
+    pub fn from_map<K: Into<String>>(m: impl IntoIterator<Item = (K, serde_json::Value)>) -> Self {
+        Self(m.into_iter().map(|(k, v)| (k.into(), v)).collect())
     }
 
     /// Returns `true` if there are no metadata entries.
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns the value for the given key.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+
+    /// Returns a mutable reference to the value for the given key.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut serde_json::Value> {
+        self.0.get_mut(key)
+    }
+
+    /// Insert an entry, returning the previous value for the key if there was one.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: serde_json::Value,
+    ) -> Option<serde_json::Value> {
+        self.0.insert(key.into(), value)
+    }
+}
+
+impl<K: Into<String>> Extend<(K, serde_json::Value)> for Metadata {
+    fn extend<T: IntoIterator<Item = (K, serde_json::Value)>>(&mut self, iter: T) {
+        self.0.extend(iter.into_iter().map(|(k, v)| (k.into(), v)))
+    }
+}
+
+/// Find the definition a ref points at, whether it's a bare name or a JSON Pointer
+/// like the ones produced with [`RefStyle::Pointer`](crate::RefStyle::Pointer).
+pub(crate) fn resolve_ref<'a>(
+    r#ref: &str,
+    definitions: &'a BTreeMap<String, Schema>,
+) -> Option<(&'a String, &'a Schema)> {
+    definitions.get_key_value(r#ref).or_else(|| {
+        let name = r#ref
+            .strip_prefix("#/definitions/")?
+            .replace("~1", "/")
+            .replace("~0", "~");
+        definitions.get_key_value(&name)
+    })
+}
+
+/// Builder for discriminator schemas. See [`Schema::discriminator`].
+#[derive(Debug, Clone)]
+pub struct DiscriminatorBuilder {
+    tag: &'static str,
+    variants: Vec<(&'static str, Schema)>,
+}
+
+impl DiscriminatorBuilder {
+    /// Add a variant. `schema` is what the rest of the object (without the tag)
+    /// has to match when the tag is `name`.
+    pub fn variant(&mut self, name: &'static str, schema: Schema) -> &mut Self {
+        self.variants.push((name, schema));
+        self
+    }
+
+    /// Validate the variants and get the discriminator schema. Per the spec,
+    /// every variant has to be a non-nullable properties schema that doesn't
+    /// declare the tag itself.
+    pub fn build(&mut self) -> Result<Schema, DiscriminatorError> {
+        let mut mapping = BTreeMap::new();
+
+        for (name, schema) in std::mem::take(&mut self.variants) {
+            check_variant(self.tag, name, &schema)?;
+
+            if mapping.insert(name, schema).is_some() {
+                return Err(DiscriminatorError::DuplicateVariant { variant: name });
+            }
+        }
+
+        Ok(Schema {
+            ty: SchemaType::Discriminator {
+                discriminator: self.tag,
+                mapping,
+            },
+            ..Schema::default()
+        })
+    }
+}
+
+fn check_variant(tag: &str, name: &'static str, schema: &Schema) -> Result<(), DiscriminatorError> {
+    if schema.nullable {
+        return Err(DiscriminatorError::Nullable { variant: name });
+    }
+
+    match &schema.ty {
+        SchemaType::Properties {
+            properties,
+            optional_properties,
+            ..
+        } => {
+            if properties.contains_key(tag) || optional_properties.contains_key(tag) {
+                return Err(DiscriminatorError::TagAsProperty { variant: name });
+            }
+            Ok(())
+        }
+        _ => Err(DiscriminatorError::NotProperties { variant: name }),
+    }
+}
+
+/// Reasons a [`DiscriminatorBuilder`] can refuse to build a schema.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum DiscriminatorError {
+    /// The variant's schema isn't in the properties form.
+    #[error("the schema of variant \"{variant}\" isn't a properties schema")]
+    NotProperties { variant: &'static str },
+    /// The variant's schema is nullable.
+    #[error("the schema of variant \"{variant}\" is nullable")]
+    Nullable { variant: &'static str },
+    /// The variant's schema declares the tag as one of its properties.
+    #[error("the schema of variant \"{variant}\" declares the tag as a property")]
+    TagAsProperty { variant: &'static str },
+    /// The same variant was added twice.
+    #[error("variant \"{variant}\" was added more than once")]
+    DuplicateVariant { variant: &'static str },
+}
+
+impl RootSchema {
+    /// Check the rules of the _Typedef_ spec that the types in this module
+    /// can't enforce on their own:
+    ///
+    /// - `enum` schemas list at least one value and no value twice,
+    /// - no property is both required and optional,
+    /// - every discriminator `mapping` entry is a non-nullable properties
+    ///   schema that doesn't declare the tag,
+    /// - every `ref` points at one of the definitions.
+    ///
+    /// Refs in the [`RefStyle::Pointer`](crate::RefStyle::Pointer) style are
+    /// accepted as long as they resolve.
+    ///
+    /// ```
+    /// use jtd_derive::schema::{RootSchema, Schema, SchemaType, ValidationError};
+    ///
+    /// let root = RootSchema {
+    ///     schema: Schema {
+    ///         ty: SchemaType::Ref { r#ref: "Foo".to_string() },
+    ///         ..Schema::default()
+    ///     },
+    ///     ..RootSchema::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     root.validate(),
+    ///     Err(ValidationError::UnresolvedRef {
+    ///         path: "".to_string(),
+    ///         r#ref: "Foo".to_string(),
+    ///     })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (name, schema) in &self.definitions {
+            validate(
+                schema,
+                &self.definitions,
+                &format!("/definitions/{}", pointer_segment(name)),
+            )?;
+        }
+        validate(&self.schema, &self.definitions, "")
+    }
+
+    /// Pretty-print this schema as JSON with a fixed key order: `definitions` first,
+    /// then the top-level schema as described in [`Schema::to_pretty_canonical_string`].
+    /// Unlike the output of [`serde_json::to_string_pretty`], this doesn't depend on
+    /// how `serde_json` orders fields, so it's suitable for golden files.
+    pub fn to_pretty_canonical_string(&self) -> String {
+        self.to_canonical(
+            self.definitions
+                .iter()
+                .map(|(key, schema)| (key.clone(), Canonical::from_schema(schema)))
+                .collect(),
+        )
+    }
+
+    fn to_canonical(&self, definitions: Vec<(String, Canonical)>) -> String {
+        let mut entries = vec![];
+        if !definitions.is_empty() {
+            entries.push(("definitions".to_string(), Canonical::Object(definitions)));
+        }
+        if let Canonical::Object(schema) = Canonical::from_schema(&self.schema) {
+            entries.extend(schema);
+        }
+
+        Canonical::Object(entries).to_pretty_string()
+    }
 }
 
-impl<A> Extend<A> for Metadata
-where
-    BTreeMap<&'static str, serde_json::Value>: Extend<A>,
-{
-    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
-        self.0.extend(iter)
+impl Schema {
+    /// Pretty-print this schema as JSON with a fixed key order: the form's keywords
+    /// (e.g. `properties`, then `optionalProperties`, then `additionalProperties`),
+    /// then `metadata`, then `nullable`. Map keys are sorted and the indentation is
+    /// two spaces.
+    ///
+    /// ```
+    /// use jtd_derive::schema::{Schema, SchemaType, TypeSchema};
+    ///
+    /// let schema = Schema {
+    ///     ty: SchemaType::Elements {
+    ///         elements: Box::new(Schema {
+    ///             ty: SchemaType::Type {
+    ///                 r#type: TypeSchema::String,
+    ///             },
+    ///             ..Schema::default()
+    ///         }),
+    ///     },
+    ///     nullable: true,
+    ///     ..Schema::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     schema.to_pretty_canonical_string(),
+    ///     "{\n  \"elements\": {\n    \"type\": \"string\"\n  },\n  \"nullable\": true\n}"
+    /// );
+    /// ```
+    pub fn to_pretty_canonical_string(&self) -> String {
+        Canonical::from_schema(self).to_pretty_string()
+    }
+}
+
+/// `path` is the JSON Pointer to `schema` within the root schema.
+fn validate(
+    schema: &Schema,
+    definitions: &BTreeMap<String, Schema>,
+    path: &str,
+) -> Result<(), ValidationError> {
+    let child = |keyword: &str, key: &str| format!("{}/{}/{}", path, keyword, pointer_segment(key));
+
+    match &schema.ty {
+        SchemaType::Empty | SchemaType::Type { .. } => Ok(()),
+        SchemaType::Enum { r#enum } => {
+            if r#enum.is_empty() {
+                return Err(ValidationError::EmptyEnum {
+                    path: path.to_string(),
+                });
+            }
+            let mut seen = HashSet::new();
+            match r#enum.iter().find(|value| !seen.insert(*value)) {
+                Some(value) => Err(ValidationError::DuplicateEnumValue {
+                    path: path.to_string(),
+                    value: value.to_string(),
+                }),
+                None => Ok(()),
+            }
+        }
+        SchemaType::Elements { elements } => {
+            validate(elements, definitions, &format!("{}/elements", path))
+        }
+        SchemaType::Properties {
+            properties,
+            optional_properties,
+            ..
+        } => {
+            if let Some(property) = properties
+                .keys()
+                .find(|key| optional_properties.contains_key(*key))
+            {
+                return Err(ValidationError::RequiredAndOptional {
+                    path: path.to_string(),
+                    property: property.to_string(),
+                });
+            }
+            for (key, schema) in properties {
+                validate(schema, definitions, &child("properties", key))?;
+            }
+            for (key, schema) in optional_properties {
+                validate(schema, definitions, &child("optionalProperties", key))?;
+            }
+            Ok(())
+        }
+        SchemaType::Values { values } => validate(values, definitions, &format!("{}/values", path)),
+        SchemaType::Discriminator {
+            discriminator,
+            mapping,
+        } => {
+            for (name, schema) in mapping {
+                check_variant(discriminator, name, schema).map_err(|source| {
+                    ValidationError::InvalidVariant {
+                        path: path.to_string(),
+                        source,
+                    }
+                })?;
+                validate(schema, definitions, &child("mapping", name))?;
+            }
+            Ok(())
+        }
+        SchemaType::Ref { r#ref } => match resolve_ref(r#ref, definitions) {
+            Some(_) => Ok(()),
+            None => Err(ValidationError::UnresolvedRef {
+                path: path.to_string(),
+                r#ref: r#ref.clone(),
+            }),
+        },
+    }
+}
+
+fn pointer_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Ways a [`RootSchema`] can violate the _Typedef_ spec. See
+/// [`RootSchema::validate`].
+///
+/// `path` is a JSON Pointer to the offending schema, e.g.
+/// `/definitions/Foo/properties/bar`. It's empty for the top-level schema.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ValidationError {
+    /// An `enum` schema without any values.
+    #[error("the enum at \"{path}\" has no values")]
+    EmptyEnum { path: String },
+    /// An `enum` schema listing the same value more than once.
+    #[error("the enum at \"{path}\" lists \"{value}\" more than once")]
+    DuplicateEnumValue { path: String, value: String },
+    /// A property that's both in `properties` and `optionalProperties`.
+    #[error("property \"{property}\" of the schema at \"{path}\" is both required and optional")]
+    RequiredAndOptional { path: String, property: String },
+    /// A discriminator `mapping` entry that isn't allowed there.
+    #[error("invalid discriminator at \"{path}\": {source}")]
+    InvalidVariant {
+        path: String,
+        source: DiscriminatorError,
+    },
+    /// A `ref` that doesn't point at any definition.
+    #[error("the ref at \"{path}\" points at a nonexistent definition \"{r#ref}\"")]
+    UnresolvedRef { path: String, r#ref: String },
+}
+
+/// JSON with the order of object keys decided up front.
+enum Canonical {
+    /// Anything that isn't an array or an object, already serialized.
+    Scalar(String),
+    Array(Vec<Canonical>),
+    Object(Vec<(String, Canonical)>),
+}
+
+impl Canonical {
+    fn from_schema(schema: &Schema) -> Self {
+        fn string(s: &str) -> Canonical {
+            Canonical::Scalar(serde_json::to_string(s).unwrap())
+        }
+
+        fn map<K: ToString>(map: &BTreeMap<K, Schema>) -> Canonical {
+            Canonical::Object(
+                map.iter()
+                    .map(|(key, schema)| (key.to_string(), Canonical::from_schema(schema)))
+                    .collect(),
+            )
+        }
+
+        let mut entries = vec![];
+        match &schema.ty {
+            SchemaType::Empty => {}
+            SchemaType::Type { r#type } => entries.push(("type", string(r#type.name()))),
+            SchemaType::Enum { r#enum } => entries.push((
+                "enum",
+                Canonical::Array(r#enum.iter().map(|v| string(v)).collect()),
+            )),
+            SchemaType::Elements { elements } => {
+                entries.push(("elements", Canonical::from_schema(elements)))
+            }
+            SchemaType::Properties {
+                properties,
+                optional_properties,
+                additional_properties,
+            } => {
+                if !properties.is_empty() {
+                    entries.push(("properties", map(properties)));
+                }
+                if !optional_properties.is_empty() {
+                    entries.push(("optionalProperties", map(optional_properties)));
+                }
+                if *additional_properties {
+                    entries.push(("additionalProperties", Canonical::Scalar("true".into())));
+                }
+            }
+            SchemaType::Values { values } => {
+                entries.push(("values", Canonical::from_schema(values)))
+            }
+            SchemaType::Discriminator {
+                discriminator,
+                mapping,
+            } => {
+                entries.push(("discriminator", string(discriminator)));
+                entries.push(("mapping", map(mapping)));
+            }
+            SchemaType::Ref { r#ref } => entries.push(("ref", string(r#ref))),
+        }
+        if !schema.metadata.is_empty() {
+            entries.push((
+                "metadata",
+                Canonical::Object(
+                    schema
+                        .metadata
+                        .0
+                        .iter()
+                        .map(|(key, value)| (key.clone(), Canonical::from_value(value)))
+                        .collect(),
+                ),
+            ));
+        }
+        if schema.nullable {
+            entries.push(("nullable", Canonical::Scalar("true".into())));
+        }
+
+        Canonical::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    fn from_value(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Array(values) => {
+                Canonical::Array(values.iter().map(Canonical::from_value).collect())
+            }
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<_> = map
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Canonical::from_value(value)))
+                    .collect();
+                entries.sort_by(|(l, _), (r, _)| l.cmp(r));
+                Canonical::Object(entries)
+            }
+            scalar => Canonical::Scalar(scalar.to_string()),
+        }
+    }
+
+    fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        fn newline(out: &mut String, depth: usize) {
+            out.push('\n');
+            out.push_str(&"  ".repeat(depth));
+        }
+
+        match self {
+            Canonical::Scalar(s) => out.push_str(s),
+            Canonical::Array(values) if values.is_empty() => out.push_str("[]"),
+            Canonical::Array(values) => {
+                out.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, depth + 1);
+                    value.write(out, depth + 1);
+                }
+                newline(out, depth);
+                out.push(']');
+            }
+            Canonical::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Canonical::Object(entries) => {
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, depth + 1);
+                    out.push_str(&serde_json::to_string(key).unwrap());
+                    out.push_str(": ");
+                    value.write(out, depth + 1);
+                }
+                newline(out, depth);
+                out.push('}');
+            }
+        }
     }
 }
 
@@ -167,6 +953,110 @@ mod tests {
         assert_eq!(serde_json::to_value(&repr).unwrap(), serde_json::json!({}))
     }
 
+    #[test]
+    fn empty_nullable() {
+        let repr = RootSchema {
+            schema: Schema {
+                ty: SchemaType::Empty,
+                nullable: true,
+                ..Schema::default()
+            },
+            definitions: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&repr).unwrap(),
+            serde_json::json!({"nullable": true})
+        )
+    }
+
+    #[test]
+    fn strip_metadata() {
+        let meta = || Metadata::from_map([("desc", json!("some docs"))]);
+        let repr = RootSchema {
+            definitions: [(
+                "foo".to_string(),
+                Schema {
+                    metadata: meta(),
+                    ty: SchemaType::Elements {
+                        elements: Box::new(Schema {
+                            metadata: meta(),
+                            ty: SchemaType::Type {
+                                r#type: TypeSchema::String,
+                            },
+                            nullable: true,
+                        }),
+                    },
+                    nullable: false,
+                },
+            )]
+            .into(),
+            schema: Schema {
+                metadata: meta(),
+                ty: SchemaType::Discriminator {
+                    discriminator: "type",
+                    mapping: [(
+                        "bar",
+                        Schema {
+                            metadata: meta(),
+                            ty: SchemaType::Properties {
+                                properties: [(
+                                    "x",
+                                    Schema {
+                                        metadata: meta(),
+                                        ty: SchemaType::Ref {
+                                            r#ref: "foo".to_string(),
+                                        },
+                                        nullable: true,
+                                    },
+                                )]
+                                .into(),
+                                optional_properties: [(
+                                    "y",
+                                    Schema {
+                                        metadata: meta(),
+                                        ty: SchemaType::Values {
+                                            values: Box::new(Schema {
+                                                metadata: meta(),
+                                                ..Schema::default()
+                                            }),
+                                        },
+                                        nullable: false,
+                                    },
+                                )]
+                                .into(),
+                                additional_properties: false,
+                            },
+                            nullable: false,
+                        },
+                    )]
+                    .into(),
+                },
+                nullable: false,
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(repr.strip_metadata()).unwrap(),
+            json!({
+                "definitions": {
+                    "foo": { "elements": { "type": "string", "nullable": true } },
+                },
+                "discriminator": "type",
+                "mapping": {
+                    "bar": {
+                        "properties": {
+                            "x": { "ref": "foo", "nullable": true },
+                        },
+                        "optionalProperties": {
+                            "y": { "values": {} },
+                        },
+                    },
+                },
+            })
+        );
+    }
+
     #[test]
     fn primitive() {
         let repr = RootSchema {
@@ -518,6 +1408,88 @@ mod tests {
         )
     }
 
+    #[test]
+    fn sub_schemas_mut() {
+        fn uppercase_desc(schema: &mut Schema) {
+            if let Some(serde_json::Value::String(desc)) = schema.metadata.get_mut("desc") {
+                *desc = desc.to_uppercase();
+            }
+            schema.sub_schemas_mut().for_each(uppercase_desc);
+        }
+
+        fn described(ty: SchemaType) -> Schema {
+            Schema {
+                metadata: Metadata::from_map([("desc", json!("some schema"))]),
+                ty,
+                nullable: false,
+            }
+        }
+
+        let string = || {
+            described(SchemaType::Type {
+                r#type: TypeSchema::String,
+            })
+        };
+
+        let mut schema = described(SchemaType::Discriminator {
+            discriminator: "type",
+            mapping: [(
+                "FOO",
+                described(SchemaType::Properties {
+                    properties: [(
+                        "elements",
+                        described(SchemaType::Elements {
+                            elements: Box::new(string()),
+                        }),
+                    )]
+                    .into(),
+                    optional_properties: [(
+                        "values",
+                        described(SchemaType::Values {
+                            values: Box::new(string()),
+                        }),
+                    )]
+                    .into(),
+                    additional_properties: false,
+                }),
+            )]
+            .into(),
+        });
+
+        uppercase_desc(&mut schema);
+
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap(),
+            serde_json::json!({
+                "metadata": { "desc": "SOME SCHEMA" },
+                "discriminator": "type",
+                "mapping": {
+                    "FOO": {
+                        "metadata": { "desc": "SOME SCHEMA" },
+                        "properties": {
+                            "elements": {
+                                "metadata": { "desc": "SOME SCHEMA" },
+                                "elements": {
+                                    "metadata": { "desc": "SOME SCHEMA" },
+                                    "type": "string"
+                                }
+                            }
+                        },
+                        "optionalProperties": {
+                            "values": {
+                                "metadata": { "desc": "SOME SCHEMA" },
+                                "values": {
+                                    "metadata": { "desc": "SOME SCHEMA" },
+                                    "type": "string"
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+
     #[test]
     fn r#ref() {
         let repr = RootSchema {
@@ -601,4 +1573,163 @@ mod tests {
             })
         )
     }
+
+    fn empty_properties() -> Schema {
+        Schema {
+            ty: SchemaType::Properties {
+                properties: [].into(),
+                optional_properties: [].into(),
+                additional_properties: false,
+            },
+            ..Schema::default()
+        }
+    }
+
+    #[test]
+    fn discriminator_builder() {
+        let schema = Schema::discriminator("type")
+            .variant("A", empty_properties())
+            .variant("B", empty_properties())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            schema,
+            Schema {
+                ty: SchemaType::Discriminator {
+                    discriminator: "type",
+                    mapping: [("A", empty_properties()), ("B", empty_properties())].into(),
+                },
+                ..Schema::default()
+            }
+        );
+    }
+
+    #[test]
+    fn discriminator_builder_invalid() {
+        let string = Schema {
+            ty: SchemaType::Type {
+                r#type: TypeSchema::String,
+            },
+            ..Schema::default()
+        };
+
+        assert_eq!(
+            Schema::discriminator("type")
+                .variant("A", empty_properties())
+                .variant("B", string.clone())
+                .build(),
+            Err(DiscriminatorError::NotProperties { variant: "B" })
+        );
+
+        assert_eq!(
+            Schema::discriminator("type")
+                .variant(
+                    "A",
+                    Schema {
+                        nullable: true,
+                        ..empty_properties()
+                    }
+                )
+                .build(),
+            Err(DiscriminatorError::Nullable { variant: "A" })
+        );
+
+        assert_eq!(
+            Schema::discriminator("type")
+                .variant(
+                    "A",
+                    Schema {
+                        ty: SchemaType::Properties {
+                            properties: [].into(),
+                            optional_properties: [("type", string)].into(),
+                            additional_properties: false,
+                        },
+                        ..Schema::default()
+                    }
+                )
+                .build(),
+            Err(DiscriminatorError::TagAsProperty { variant: "A" })
+        );
+
+        assert_eq!(
+            Schema::discriminator("type")
+                .variant("A", empty_properties())
+                .variant("A", empty_properties())
+                .build(),
+            Err(DiscriminatorError::DuplicateVariant { variant: "A" })
+        );
+    }
+
+    #[test]
+    fn pretty_canonical() {
+        let repr = RootSchema {
+            definitions: [(
+                "Foo".to_string(),
+                Schema {
+                    ty: SchemaType::Enum {
+                        r#enum: vec!["A", "B"],
+                    },
+                    ..Schema::default()
+                },
+            )]
+            .into(),
+            schema: Schema {
+                metadata: Metadata::from_map([(
+                    "description",
+                    json!({ "z": [1, 2], "a": "\"quoted\"" }),
+                )]),
+                ty: SchemaType::Properties {
+                    properties: [(
+                        "foo",
+                        Schema {
+                            ty: SchemaType::Ref {
+                                r#ref: "Foo".to_string(),
+                            },
+                            nullable: true,
+                            ..Schema::default()
+                        },
+                    )]
+                    .into(),
+                    optional_properties: [("bar", Schema::default())].into(),
+                    additional_properties: true,
+                },
+                nullable: true,
+            },
+        };
+
+        assert_eq!(
+            repr.to_pretty_canonical_string(),
+            r#"{
+  "definitions": {
+    "Foo": {
+      "enum": [
+        "A",
+        "B"
+      ]
+    }
+  },
+  "properties": {
+    "foo": {
+      "ref": "Foo",
+      "nullable": true
+    }
+  },
+  "optionalProperties": {
+    "bar": {}
+  },
+  "additionalProperties": true,
+  "metadata": {
+    "description": {
+      "a": "\"quoted\"",
+      "z": [
+        1,
+        2
+      ]
+    }
+  },
+  "nullable": true
+}"#
+        );
+    }
 }
@@ -0,0 +1,88 @@
+//! Comparing schemas against files committed to the repo.
+
+use std::path::Path;
+
+use crate::schema::RootSchema;
+
+/// The environment variable that makes [`assert_schema_matches!`](crate::assert_schema_matches)
+/// (re)write the snapshot files instead of comparing against them.
+pub const OVERWRITE_ENV_VAR: &str = "JTD_DERIVE_OVERWRITE";
+
+/// Assert that the schema generated for a type matches the JSON in a file,
+/// so that changes to the schema don't go unnoticed. The path is relative to the
+/// directory of the crate's `Cargo.toml`.
+///
+/// If the `JTD_DERIVE_OVERWRITE` environment variable is set, the file is written
+/// instead, so that an intentional change can be reviewed and committed.
+///
+/// By default, [`Generator::default()`](crate::Generator::default) is used.
+/// A differently configured generator can be passed as the third argument.
+///
+/// ```no_run
+/// use jtd_derive::{assert_schema_matches, Generator, JsonTypedef};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// assert_schema_matches!(Foo, "tests/schemas/foo.json");
+/// assert_schema_matches!(
+///     Foo,
+///     "tests/schemas/foo_short.json",
+///     Generator::builder().naming_short().build()
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_schema_matches {
+    ($ty:ty, $path:expr $(,)?) => {
+        $crate::assert_schema_matches!($ty, $path, $crate::Generator::default())
+    };
+    ($ty:ty, $path:expr, $generator:expr $(,)?) => {
+        $crate::__assert_schema_matches(
+            $crate::Generator::into_root_schema::<$ty>($generator)
+                .expect("failed to generate the schema"),
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path),
+        )
+    };
+}
+
+#[doc(hidden)]
+pub fn __assert_schema_matches(schema: RootSchema, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let actual = serde_json::to_value(&schema).expect("schemas are always serializable");
+
+    if std::env::var_os(OVERWRITE_ENV_VAR).is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        // serializing the schema itself rather than the `Value` keeps the usual
+        // order of keywords, which is easier to read
+        let json = serde_json::to_string_pretty(&schema).unwrap();
+        std::fs::write(path, json + "\n")
+            .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "failed to read {}: {}\nrun with {}=1 to create it",
+            path.display(),
+            e,
+            OVERWRITE_ENV_VAR
+        )
+    });
+    let expected: serde_json::Value = serde_json::from_str(&expected)
+        .unwrap_or_else(|e| panic!("{} isn't valid JSON: {}", path.display(), e));
+
+    if actual != expected {
+        panic!(
+            "the schema doesn't match {}\n\nexpected:\n{}\n\nactual:\n{}\n\n\
+             run with {}=1 to overwrite the file if the change is intended",
+            path.display(),
+            serde_json::to_string_pretty(&expected).unwrap(),
+            serde_json::to_string_pretty(&actual).unwrap(),
+            OVERWRITE_ENV_VAR
+        );
+    }
+}
@@ -4,10 +4,11 @@ use std::cmp::Reverse;
 use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
 use std::fmt::Arguments;
 use std::ops::{Range, RangeInclusive};
-use std::sync::{atomic, Mutex, RwLock};
+use std::rc::Rc;
+use std::sync::{atomic, Arc, Mutex, RwLock};
 
-use crate::schema::{Schema, SchemaType, TypeSchema};
-use crate::{Generator, Names};
+use crate::schema::{Metadata, RootSchema, Schema, SchemaType, TypeSchema};
+use crate::{BigInts, GenError, Generator, Names};
 
 pub use jtd_derive_macros::JsonTypedef;
 
@@ -26,9 +27,90 @@ pub trait JsonTypedef {
     /// Returns info about how to refer to this type within the
     /// [_Typedef_](https://jsontypedef.com/) schema.
     /// Mostly used to generate a name for the top-level definitions.
+    ///
+    /// This doesn't involve a [`Generator`] or any schema generation, so it's cheap
+    /// to call on its own, e.g. to identify a type when registering it somewhere.
+    /// The derived implementation only calls `names()` of the type parameters.
     fn names() -> Names;
+
+    /// Generate the root schema of this type with [`Generator::default()`]. A
+    /// shorthand for `Generator::default().into_root_schema::<Self>()`.
+    ///
+    /// ```
+    /// use jtd_derive::JsonTypedef;
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     x: u32,
+    /// }
+    ///
+    /// let json_schema = serde_json::to_value(Foo::schema_default().unwrap()).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "properties": {
+    ///         "x": { "type": "uint32" }
+    ///     },
+    ///     "additionalProperties": true,
+    /// } });
+    /// ```
+    fn schema_default() -> Result<RootSchema, GenError>
+    where
+        Self: Sized,
+    {
+        Generator::default().into_root_schema::<Self>()
+    }
 }
 
+/// Types that can be the keys of maps with a schema in the
+/// [values form](https://jsontypedef.com/docs/jtd-in-5-minutes/#values-schemas).
+///
+/// JSON object keys are always strings, so this is for types that serialize as
+/// strings, or as something `serde_json` turns into a string when it's used as
+/// a key. That's why integer types of any size are included, regardless of
+/// the [`BigInts`] setting - as keys, they can't lose precision.
+///
+/// Deriving [`JsonTypedef`] implements this for enums with only unit variants
+/// that are serialized as strings, i.e. that aren't tagged or `untagged`, and
+/// don't use `repr` or `null_variant`.
+///
+/// For other types that serialize as strings, e.g. newtypes with custom
+/// `Serialize` impls, derive it with `#[typedef(map_key)]` or implement this
+/// manually.
+pub trait JsonTypedefMapKey {}
+
+macro_rules! impl_map_keys {
+	($($in:ty),*) => {
+		$(
+            impl JsonTypedefMapKey for $in {}
+		)*
+	};
+}
+
+impl_map_keys!(
+    String,
+    str,
+    char,
+    Box<str>,
+    Rc<str>,
+    Arc<str>,
+    i8,
+    u8,
+    i16,
+    u16,
+    i32,
+    u32,
+    i64,
+    u64,
+    i128,
+    u128,
+    isize,
+    usize
+);
+
+impl<T: JsonTypedefMapKey + ?Sized> JsonTypedefMapKey for &T {}
+
+impl JsonTypedefMapKey for Cow<'_, str> {}
+
 macro_rules! impl_primitives {
 	($($in:ty => $out:ident),*) => {
 		$(
@@ -47,13 +129,7 @@ macro_rules! impl_primitives {
                 }
 
                 fn names() -> Names {
-                    Names {
-                        short: TypeSchema::$out.name(),
-                        long: TypeSchema::$out.name(),
-                        nullable: false,
-                        type_params: vec![],
-                        const_params: vec![],
-                    }
+                    Names::new(TypeSchema::$out.name(), TypeSchema::$out.name())
                 }
             }
         )*
@@ -78,11 +154,77 @@ impl_primitives! {
     atomic::AtomicI8 => Int8,
     atomic::AtomicI16 => Int16,
     atomic::AtomicI32 => Int32,
-    char => String,
     String => String,
     str => String
 }
 
+macro_rules! impl_big_ints {
+	($($in:ty),*) => {
+		$(
+            /// _Typedef_ has no integer type this big, so what this does depends
+            /// on the generator's [`BigInts`] setting. By default, generation fails.
+            impl JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    big_int::<Self>(gen)
+                }
+
+                fn referenceable() -> bool {
+                    false
+                }
+
+                fn names() -> Names {
+                    Names::new(stringify!($in), stringify!($in))
+                }
+            }
+        )*
+	};
+}
+
+impl_big_ints!(u64, i64, u128, i128, usize, isize);
+
+/// The schema of an integer type too big for any _Typedef_ type, according to
+/// [`BigInts`].
+fn big_int<T: JsonTypedef + ?Sized>(gen: &mut Generator) -> Schema {
+    match gen.big_ints() {
+        BigInts::Reject => gen.reject::<T>(
+            "no Typedef type fits integers this big; \
+             see `GeneratorBuilder::big_ints` for describing them as `float64`",
+        ),
+        BigInts::Float64 => {
+            gen.warn::<T>("described as `float64`, which only holds integers up to 2^53 exactly")
+        }
+    }
+
+    Schema {
+        ty: SchemaType::Type {
+            r#type: TypeSchema::Float64,
+        },
+        ..Schema::default()
+    }
+}
+
+impl JsonTypedef for char {
+    fn schema(gen: &mut Generator) -> Schema {
+        gen.warn::<Self>(
+            "a `char` is serialized as a single character, but the schema allows any string",
+        );
+        Schema {
+            ty: SchemaType::Type {
+                r#type: TypeSchema::String,
+            },
+            ..Schema::default()
+        }
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names::new("string", "string")
+    }
+}
+
 // Distinct types due to additional constraints
 macro_rules! impl_wrappers {
 	($($($path_parts:ident)::+ => $in:ident => $out:ident),*) => {
@@ -102,13 +244,7 @@ macro_rules! impl_wrappers {
                 }
 
                 fn names() -> Names {
-                    Names {
-                        short: stringify!($in),
-                        long: stringify!($($path_parts)::+::$in),
-                        nullable: false,
-                        type_params: vec![],
-                        const_params: vec![],
-                    }
+                    Names::new(stringify!($in), concat!($(stringify!($path_parts), "::",)+ stringify!($in)))
                 }
             }
         )*
@@ -128,9 +264,32 @@ impl_wrappers! {
     std::net => Ipv6Addr => String,
     std::net => SocketAddr => String,
     std::net => SocketAddrV4 => String,
-    std::net => SocketAddrV6 => String,
+    std::net => SocketAddrV6 => String
+}
+
+impl JsonTypedef for std::path::Path {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema {
+            metadata: Metadata::from_map([(
+                "description",
+                "A file system path. Serializing a path that isn't valid Unicode fails, \
+                 which depends on the platform it was created on."
+                    .into(),
+            )]),
+            ty: SchemaType::Type {
+                r#type: TypeSchema::String,
+            },
+            ..Schema::default()
+        }
+    }
 
-    std::path => Path => String
+    fn referenceable() -> bool {
+        true
+    }
+
+    fn names() -> Names {
+        Names::new("Path", "std::path::Path")
+    }
 }
 
 impl JsonTypedef for std::path::PathBuf {
@@ -147,6 +306,42 @@ impl JsonTypedef for std::path::PathBuf {
     }
 }
 
+/// Matches `serde`'s default representation,
+/// `{"secs_since_epoch": ..., "nanos_since_epoch": ...}`.
+///
+/// `secs_since_epoch` is a `u64`, so it's subject to the same [`BigInts`]
+/// setting as plain `u64`s. By default, generation fails. With
+/// [`BigInts::Float64`], the seconds are described as `float64`, which is only
+/// exact up to 2^53 seconds, roughly 285 million years after the epoch.
+impl JsonTypedef for std::time::SystemTime {
+    fn schema(gen: &mut Generator) -> Schema {
+        Schema {
+            ty: SchemaType::Properties {
+                properties: [
+                    ("secs_since_epoch", big_int::<Self>(gen)),
+                    ("nanos_since_epoch", gen.sub_schema::<u32>()),
+                ]
+                .into(),
+                optional_properties: [].into(),
+                additional_properties: false,
+            },
+            ..Schema::default()
+        }
+    }
+
+    fn referenceable() -> bool {
+        true
+    }
+
+    fn names() -> Names {
+        Names::new("SystemTime", "std::time::SystemTime")
+    }
+}
+
+// `nullable` is allowed on any form. For a discriminator this means the value is
+// either `null` or one of the tagged objects - only the mapping entries themselves
+// can't be nullable. Some code generators still handle nullable discriminators
+// poorly, e.g. by dropping the `null` case.
 impl<T: JsonTypedef> JsonTypedef for Option<T> {
     fn schema(gen: &mut Generator) -> Schema {
         let mut schema = gen.sub_schema::<T>();
@@ -183,28 +378,71 @@ macro_rules! impl_array_like {
                 }
 
                 fn names() -> Names {
-                    Names {
-                        short: "array",
-                        long: "array",
-                        nullable: false,
-                        type_params: vec![T::names()],
-                        const_params: vec![],
+                    Names::new("array", "array").with_type_params([T::names()])
+                }
+            }
+        )*
+	};
+}
+
+impl_array_like!(Vec<T>, VecDeque<T>, BinaryHeap<T>, LinkedList<T>, [T]);
+
+macro_rules! impl_bytes {
+	($($in:ty),*) => {
+		$(
+            /// The same as `Vec<u8>`: `bytes` serializes these as a sequence of
+            /// bytes, which `serde_json` turns into an array of numbers.
+            ///
+            /// Fields that are serialized some other way, e.g. as a base64
+            /// string with `#[serde(with = "...")]`, should say so with
+            /// `#[typedef(type = "String")]`.
+            #[cfg(feature = "bytes")]
+            impl JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.sub_schema::<Vec<u8>>()
+                }
+
+                fn referenceable() -> bool {
+                    false
+                }
+
+                fn names() -> Names {
+                    Vec::<u8>::names()
+                }
+            }
+		)*
+	};
+}
+
+impl_bytes!(bytes::Bytes, bytes::BytesMut);
+
+macro_rules! impl_set_like {
+	($($in:ty),*) => {
+		$(
+            impl<T: JsonTypedef> JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.warn::<Self>("set elements are unique, but the schema allows duplicates");
+                    Schema {
+                        ty: SchemaType::Elements {
+                            elements: Box::new(gen.sub_schema::<T>()),
+                        },
+                        ..Schema::default()
                     }
                 }
+
+                fn referenceable() -> bool {
+                    false
+                }
+
+                fn names() -> Names {
+                    Names::new("array", "array").with_type_params([T::names()])
+                }
             }
         )*
 	};
 }
 
-impl_array_like!(
-    Vec<T>,
-    VecDeque<T>,
-    std::collections::BTreeSet<T>,
-    BinaryHeap<T>,
-    HashSet<T>,
-    LinkedList<T>,
-    [T]
-);
+impl_set_like!(std::collections::BTreeSet<T>, HashSet<T>);
 
 impl<T: JsonTypedef, const N: usize> JsonTypedef for [T; N] {
     fn schema(gen: &mut Generator) -> Schema {
@@ -221,20 +459,14 @@ impl<T: JsonTypedef, const N: usize> JsonTypedef for [T; N] {
     }
 
     fn names() -> Names {
-        Names {
-            short: "array",
-            long: "array",
-            nullable: false,
-            type_params: vec![T::names()],
-            const_params: vec![],
-        }
+        Names::new("array", "array").with_type_params([T::names()])
     }
 }
 
 macro_rules! impl_map_like {
 	($($in:ty),*) => {
 		$(
-            impl<K: ToString, V: JsonTypedef> JsonTypedef for $in {
+            impl<K: JsonTypedefMapKey, V: JsonTypedef> JsonTypedef for $in {
                 fn schema(gen: &mut Generator) -> Schema {
                     Schema {
                         ty: SchemaType::Values {
@@ -249,13 +481,7 @@ macro_rules! impl_map_like {
                 }
 
                 fn names() -> Names {
-                    Names {
-                        short: "map",
-                        long: "map",
-                        nullable: false,
-                        type_params: vec![V::names()],
-                        const_params: vec![],
-                    }
+                    Names::new("map", "map").with_type_params([V::names()])
                 }
             }
         )*
@@ -288,12 +514,34 @@ impl_transparent!(
     std::num::Wrapping<T>,
     Cell<T>,
     RefCell<T>,
-    Box<T>,
     Mutex<T>,
     RwLock<T>,
     Reverse<T>
 );
 
+// Smart pointers can point to unsized types, e.g. `Box<str>` or `Arc<[T]>`
+macro_rules! impl_transparent_unsized {
+	($($in:ty),*) => {
+		$(
+            impl<T: JsonTypedef + ?Sized> JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.sub_schema::<T>()
+                }
+
+                fn referenceable() -> bool {
+                    false
+                }
+
+                fn names() -> Names {
+                    T::names()
+                }
+            }
+        )*
+	};
+}
+
+impl_transparent_unsized!(Box<T>, Rc<T>, Arc<T>);
+
 macro_rules! impl_transparent_lifetime {
 	($($in:ty),*) => {
 		$(
@@ -345,14 +593,48 @@ impl<'a> JsonTypedef for Arguments<'a> {
     }
 
     fn names() -> Names {
-        Names {
-            short: "string",
-            long: "string",
-            nullable: false,
-            type_params: vec![],
-            const_params: vec![],
+        Names::new("string", "string")
+    }
+}
+
+impl JsonTypedef for serde_json::Value {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema::default()
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names::new("any", "any")
+    }
+}
+
+/// Any JSON number, described as `float64`, the most permissive numeric type
+/// _Typedef_ has.
+///
+/// Integers beyond 2^53 and `arbitrary_precision` numbers are still accepted by
+/// validators, but consumers that read the value as a `float64` may lose
+/// precision. Unlike the big integer types, this doesn't depend on
+/// [`BigInts`], since a `Number` makes no promise of being an integer.
+impl JsonTypedef for serde_json::Number {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema {
+            ty: SchemaType::Type {
+                r#type: TypeSchema::Float64,
+            },
+            ..Schema::default()
         }
     }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names::new("float64", "float64")
+    }
 }
 
 macro_rules! impl_range {
@@ -375,13 +657,7 @@ macro_rules! impl_range {
                 }
 
                 fn names() -> Names {
-                    Names {
-                        short: stringify!($in),
-                        long: concat!("std::ops::", stringify!($in)),
-                        nullable: false,
-                        type_params: vec![T::names()],
-                        const_params: vec![],
-                    }
+                    Names::new(stringify!($in), concat!("std::ops::", stringify!($in))).with_type_params([T::names()])
                 }
             }
         )*
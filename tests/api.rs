@@ -0,0 +1,149 @@
+//! An end-to-end check modeled on a small REST API: the types are derived,
+//! bundled into one document with shared definitions, and sample payloads
+//! produced by serde are checked against the result.
+
+use std::collections::HashMap;
+
+use jtd_derive::schema::RootSchema;
+use jtd_derive::{register_schema, Generator, JsonTypedef, SchemaRegistry};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(JsonTypedef, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    id: u32,
+    display_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[typedef(optional)]
+    email: Option<String>,
+    address: Address,
+    settings: HashMap<String, String>,
+}
+
+#[derive(JsonTypedef, Serialize)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(JsonTypedef, Serialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+enum Event {
+    UserCreated { user: User },
+    UserDeleted { id: u32 },
+}
+
+#[derive(JsonTypedef, Serialize)]
+struct EventPage {
+    events: Vec<Event>,
+    next_cursor: Option<String>,
+}
+
+fn bundle() -> RootSchema {
+    let mut registry = SchemaRegistry::new();
+    register_schema!(registry, EventPage);
+
+    registry
+        .into_bundle(Generator::builder().naming_short().build())
+        .unwrap()
+}
+
+/// Validates `instance` against the definition `def` of the bundle with the
+/// reference implementation.
+fn is_valid(root: &RootSchema, def: &str, instance: &Value) -> bool {
+    let mut doc = serde_json::to_value(root).unwrap();
+    doc["ref"] = def.into();
+
+    let schema = jtd::Schema::from_serde_schema(serde_json::from_value(doc).unwrap()).unwrap();
+    schema.validate().unwrap();
+
+    jtd::validate(&schema, instance, Default::default())
+        .unwrap()
+        .is_empty()
+}
+
+#[test]
+fn bundle_shares_definitions() {
+    let root = bundle();
+
+    assert_eq!(
+        root.definitions.keys().collect::<Vec<_>>(),
+        ["Address", "Event", "EventPage", "User"]
+    );
+    assert_eq!(
+        serde_json::to_value(&root.definitions["Event"]).unwrap(),
+        json!({
+            "discriminator": "type",
+            "mapping": {
+                "USER_CREATED": {
+                    "properties": { "user": { "ref": "User" } },
+                    "additionalProperties": true,
+                },
+                "USER_DELETED": {
+                    "properties": { "id": { "type": "uint32" } },
+                    "additionalProperties": true,
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn sample_payloads_conform() {
+    let root = bundle();
+
+    let user = User {
+        id: 7,
+        display_name: "Ferris".to_string(),
+        email: None,
+        address: Address {
+            street: "1 Crab Lane".to_string(),
+            city: "Rustville".to_string(),
+        },
+        settings: HashMap::from([("theme".to_string(), "dark".to_string())]),
+    };
+    let page = EventPage {
+        events: vec![Event::UserCreated { user }, Event::UserDeleted { id: 7 }],
+        next_cursor: None,
+    };
+
+    let user = serde_json::to_value(match &page.events[0] {
+        Event::UserCreated { user } => user,
+        _ => unreachable!(),
+    })
+    .unwrap();
+    let page = serde_json::to_value(&page).unwrap();
+
+    assert!(is_valid(&root, "User", &user));
+    assert!(is_valid(&root, "EventPage", &page));
+}
+
+#[test]
+fn invalid_payloads_rejected() {
+    let root = bundle();
+
+    // missing required `address`
+    assert!(!is_valid(
+        &root,
+        "User",
+        &json!({ "id": 1, "displayName": "x", "settings": {} })
+    ));
+    // unknown event tag
+    assert!(!is_valid(
+        &root,
+        "EventPage",
+        &json!({ "events": [{ "type": "USER_BANNED", "id": 1 }], "next_cursor": null })
+    ));
+    // settings values must be strings
+    assert!(!is_valid(
+        &root,
+        "User",
+        &json!({
+            "id": 1,
+            "displayName": "x",
+            "address": { "street": "a", "city": "b" },
+            "settings": { "theme": 1 },
+        })
+    ));
+}
@@ -0,0 +1,44 @@
+use jtd_derive::build::{emit_schemas, to_schema_string};
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct UserProfile {
+    id: u32,
+}
+
+#[test]
+fn emit_schemas_to_file() {
+    let dir = std::env::temp_dir().join(format!("jtd-derive-build-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let out = dir.join("schemas.rs");
+
+    let schemas = [
+        ("UserProfile", to_schema_string::<UserProfile>().unwrap()),
+        ("ids", to_schema_string::<Vec<u32>>().unwrap()),
+    ];
+    emit_schemas(&schemas, &out).unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&out).unwrap(),
+        r#"// @generated by jtd_derive::build::emit_schemas
+
+pub const USER_PROFILE_SCHEMA: &str = "{\n  \"properties\": {\n    \"id\": {\n      \"type\": \"uint32\"\n    }\n  },\n  \"additionalProperties\": true\n}";
+
+pub const IDS_SCHEMA: &str = "{\n  \"elements\": {\n    \"type\": \"uint32\"\n  }\n}";
+"#
+    );
+
+    // regenerating doesn't touch the file
+    let modified = std::fs::metadata(&out).unwrap().modified().unwrap();
+    emit_schemas(&schemas, &out).unwrap();
+    assert_eq!(
+        std::fs::metadata(&out).unwrap().modified().unwrap(),
+        modified
+    );
+
+    let err = emit_schemas(&[("user_profile", "{}"), ("UserProfile", "{}")], &out).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
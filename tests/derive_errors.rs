@@ -15,3 +15,9 @@ fn union_errors() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/derive_errors/*.rs");
 }
+
+#[test]
+fn attr_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/derive_errors/attrs/*.rs");
+}
@@ -0,0 +1,25 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag, deny_unknown_fields = "yes", transparent(x), default = true)]
+enum Flags {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = 1, from = 2, try_from = 3, rename_all = 4, metadata_from = 5)]
+enum NotStrings {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(from, try_from, rename_all, metadata_from, enum = "foo")]
+enum MissingValues {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(rename_all = "SpOnGeCaSe")]
+struct UnknownRenameRule {
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(from = "u32", try_from = "u32")]
+struct Foo {
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(jtd_derive::JsonTypedef)]
+struct Foo {
+    #[typedef(metadata(foo, bar = 2))]
+    x: u32,
+    #[typedef(metadata = "a")]
+    y: u32,
+}
+
+fn main() {}
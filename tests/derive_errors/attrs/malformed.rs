@@ -0,0 +1,19 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef = "foo"]
+struct NotAList {
+    x: u32,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef("foo")]
+struct Literal {
+    x: u32,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(foo::bar)]
+struct Path {
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(deny_unkown_fields)]
+struct Container {
+    x: u32,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+struct Field {
+    #[typedef(renam = "y")]
+    x: u32,
+    #[typedef(optinal)]
+    z: Option<u32>,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum Variant {
+    #[typedef(tagvalue = "bar")]
+    Bar { x: u32 },
+}
+
+fn main() {}
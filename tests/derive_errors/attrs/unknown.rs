@@ -0,0 +1,20 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(foo)]
+struct Container {
+    x: u32,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+struct Field {
+    #[typedef(foo)]
+    x: u32,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum Variant {
+    #[typedef(foo)]
+    Bar { x: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum Foo {
+    #[typedef(tag_value)]
+    Bar { x: u32 },
+    #[typedef(tag_value = 5)]
+    Baz { x: u32 },
+}
+
+fn main() {}
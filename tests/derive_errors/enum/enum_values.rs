@@ -0,0 +1,32 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(enum())]
+enum Empty {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(enum("foo", "bar", "foo"))]
+enum Duplicate {
+    Foo,
+    Bar,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(enum("foo", 5))]
+enum NotString {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type", enum("foo"))]
+enum StructVariants {
+    Foo { x: u32 },
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(enum("foo"))]
+struct Struct {
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(serde::Serialize, jtd_derive::JsonTypedef)]
+enum Foo {
+    Bar { x: u32 },
+    Baz { y: String },
+}
+
+fn main() {}
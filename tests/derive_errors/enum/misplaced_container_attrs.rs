@@ -0,0 +1,25 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(transparent)]
+enum Transparent {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(default)]
+enum Default {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(transparent_elements = "u32")]
+enum TransparentElements {
+    Foo,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(as_values)]
+enum AsValues {
+    Foo,
+}
+
+fn main() {}
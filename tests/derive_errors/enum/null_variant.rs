@@ -0,0 +1,25 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum TwoNullVariants {
+    #[typedef(null_variant)]
+    Null,
+    #[typedef(null_variant)]
+    None,
+    Value { v: u32 },
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum StructNullVariant {
+    #[typedef(null_variant)]
+    Null { x: u32 },
+    Value { v: u32 },
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+enum OnlyNullVariant {
+    #[typedef(null_variant)]
+    Null,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(repr = "uint8")]
+enum TooBig {
+    Small = 255,
+    Big,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(repr = "uint8")]
+enum NotLiteral {
+    A = 1 + 1,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(repr = "uint64")]
+enum UnsupportedRepr {
+    A,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(repr = "uint8", tag = "type")]
+enum Tagged {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type", single_variant_as_properties)]
+enum TwoVariants {
+    Bar { x: u32 },
+    Baz { y: u32 },
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(single_variant_as_properties)]
+enum Untagged {
+    Bar,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum TagValue {
+    Foo { x: u32 },
+    #[typedef(tag_value = "Foo")]
+    Bar { y: u32 },
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(rename_all = "lowercase")]
+enum RenameAll {
+    FooBar,
+    Foobar,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(jtd_derive::JsonTypedef)]
+enum Foo {
+    #[typedef(tag_value = "bar")]
+    Bar,
+    Baz,
+}
+
+fn main() {}
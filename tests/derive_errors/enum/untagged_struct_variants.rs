@@ -0,0 +1,8 @@
+#[derive(serde::Deserialize, jtd_derive::JsonTypedef)]
+#[serde(untagged)]
+enum Foo {
+    Bar(u32),
+    Baz { x: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(as_values)]
+struct Mixed {
+    a: u32,
+    b: Option<u32>,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(as_values)]
+struct AllSkipped {
+    #[typedef(skip)]
+    a: u32,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(as_values)]
+struct Tuple(u32);
+
+fn main() {}
@@ -0,0 +1,14 @@
+use std::collections::HashMap;
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum Tagged {
+    Bar,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+struct Foo {
+    map: HashMap<Tagged, u32>,
+}
+
+fn main() {}
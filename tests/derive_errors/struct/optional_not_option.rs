@@ -0,0 +1,7 @@
+#[derive(jtd_derive::JsonTypedef)]
+struct Foo {
+    #[typedef(optional)]
+    x: Vec<Option<u32>>,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(rename_all = "camelCase")]
+struct RenameAll {
+    foo_bar: u32,
+    #[typedef(rename = "fooBar")]
+    other: u32,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
+enum Variant {
+    Bar {
+        #[typedef(rename = "y")]
+        x: u32,
+        y: u32,
+    },
+}
+
+fn main() {}
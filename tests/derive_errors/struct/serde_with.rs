@@ -0,0 +1,20 @@
+mod as_string {
+    pub fn serialize<S: serde::Serializer>(x: &u32, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(x)
+    }
+}
+
+#[derive(serde::Serialize, jtd_derive::JsonTypedef)]
+struct Foo {
+    #[serde(serialize_with = "as_string::serialize")]
+    x: u32,
+}
+
+#[derive(serde::Serialize, jtd_derive::JsonTypedef)]
+struct Bar {
+    #[serde(serialize_with = "as_string::serialize")]
+    #[typedef(type = "String", schema_with = "some_fn")]
+    x: u32,
+}
+
+fn main() {}
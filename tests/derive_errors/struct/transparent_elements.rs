@@ -0,0 +1,11 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(transparent_elements = "u32")]
+struct Named {
+    ids: Vec<u32>,
+}
+
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(transparent_elements = "u32")]
+struct TwoFields(Vec<u32>, u32);
+
+fn main() {}
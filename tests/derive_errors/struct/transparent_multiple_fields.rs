@@ -0,0 +1,8 @@
+#[derive(jtd_derive::JsonTypedef)]
+#[typedef(transparent)]
+struct Foo {
+    x: u32,
+    y: u32,
+}
+
+fn main() {}
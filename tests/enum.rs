@@ -84,3 +84,395 @@ fn enum_struct_variants() {
         }}
     );
 }
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum TagValues {
+    #[typedef(tag_value = "v2")]
+    FooBar {
+        x: u32,
+    },
+    BazQux {
+        y: String,
+    },
+}
+
+#[test]
+fn enum_tag_value() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<TagValues>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "v2": {
+                    "properties": {
+                        "x": {"type": "uint32"}
+                    },
+                    "additionalProperties": true
+                },
+                "baz_qux": {
+                    "properties": {
+                        "y": {"type": "string"}
+                    },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "kind")]
+#[allow(dead_code)]
+enum UnitTagValues {
+    #[typedef(tag_value = "bar-1")]
+    Bar,
+    Baz,
+}
+
+#[test]
+fn enum_unit_variants_tag_value() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<UnitTagValues>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "kind": {"enum": ["bar-1", "Baz"]}
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(enum("a", "b-c", "123"))]
+#[allow(dead_code)]
+enum EnumOverride {
+    A,
+    BC,
+    OneTwoThree,
+}
+
+#[test]
+fn enum_values_override() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<EnumOverride>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "enum": ["a", "b-c", "123"]
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", single_variant_as_properties)]
+#[allow(dead_code)]
+enum SingleVariant {
+    Bar { x: u32 },
+}
+
+#[test]
+fn enum_single_variant_as_properties() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<SingleVariant>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "type": {"enum": ["Bar"]},
+                "x": {"type": "uint32"}
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(rename_all = "lowercase")]
+#[allow(dead_code)]
+enum RenamedVariants {
+    #[typedef(rename = "Foo Bar-1.0")]
+    FooBar,
+    Baz,
+}
+
+#[test]
+fn enum_renamed_variants() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<RenamedVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "enum": ["Foo Bar-1.0", "baz"]
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum VariantRenameAll {
+    #[typedef(rename_all = "camelCase")]
+    FooBar { some_field: u32 },
+    #[typedef(rename_all = "kebab-case")]
+    BazQux { some_field: u32 },
+}
+
+#[test]
+fn enum_variant_rename_all() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<VariantRenameAll>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "foo_bar": {
+                    "properties": {
+                        "someField": {"type": "uint32"}
+                    },
+                    "additionalProperties": true
+                },
+                "baz_qux": {
+                    "properties": {
+                        "some-field": {"type": "uint32"}
+                    },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
+
+#[test]
+fn enum_include_tag_in_variants() {
+    let gen_schema = |generator: Generator| {
+        serde_json::to_value(generator.into_root_schema::<StructVariants>().unwrap()).unwrap()
+    };
+
+    assert_eq!(
+        gen_schema(Generator::default())["mapping"]["Bar"],
+        serde_json::json! {{
+            "properties": {
+                "x": {"type": "uint32"}
+            },
+            "additionalProperties": true
+        }}
+    );
+    assert_eq!(
+        gen_schema(Generator::builder().include_tag_in_variants().build()),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Bar": {
+                    "properties": {
+                        "type": {"type": "string"},
+                        "x": {"type": "uint32"}
+                    },
+                    "additionalProperties": true
+                },
+                "Baz": {
+                    "properties": {
+                        "type": {"type": "string"},
+                        "y": {"type": "string"}
+                    },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(repr = "uint8")]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Level {
+    Low = 1,
+    Medium,
+    High = 10,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(repr = "int16")]
+#[repr(i16)]
+#[allow(dead_code)]
+enum Offset {
+    Back = -1,
+    Still,
+    Forward,
+}
+
+#[test]
+fn repr() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Level>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "type": "uint8",
+            "metadata": { "values": [1, 2, 10] },
+        }}
+    );
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Offset>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "type": "int16",
+            "metadata": { "values": [-1, 0, 1] },
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum MaybeValue {
+    #[typedef(null_variant)]
+    Null,
+    Value {
+        v: u32,
+    },
+    Pair {
+        a: u32,
+        b: u32,
+    },
+}
+
+#[test]
+fn null_variant() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<MaybeValue>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Value": {
+                    "properties": { "v": { "type": "uint32" } },
+                    "additionalProperties": true,
+                },
+                "Pair": {
+                    "properties": {
+                        "a": { "type": "uint32" },
+                        "b": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "nullable": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct OptionalTagged {
+    inner: Option<StructVariants>,
+}
+
+#[test]
+fn option_of_tagged_enum() {
+    let expected = serde_json::json! {{
+        "properties": {
+            "inner": {
+                "discriminator": "type",
+                "mapping": {
+                    "Bar": {
+                        "properties": {
+                            "x": {"type": "uint32"}
+                        },
+                        "additionalProperties": true
+                    },
+                    "Baz": {
+                        "properties": {
+                            "y": {"type": "string"}
+                        },
+                        "additionalProperties": true
+                    }
+                },
+                "nullable": true
+            }
+        },
+        "additionalProperties": true
+    }};
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .build()
+                .into_root_schema::<OptionalTagged>()
+                .unwrap()
+        )
+        .unwrap(),
+        expected
+    );
+
+    // a nullable discriminator is valid, only the mapping entries can't be nullable
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .strict()
+                .build()
+                .into_root_schema::<OptionalTagged>()
+                .unwrap()
+        )
+        .unwrap(),
+        expected
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct EnumKeyed {
+    hash_map: std::collections::HashMap<UnitVariants, u32>,
+    btree_map: std::collections::BTreeMap<UnitVariants, String>,
+}
+
+#[test]
+fn enum_keyed_maps() {
+    fn assert_map_key<K: jtd_derive::JsonTypedefMapKey>() {}
+    assert_map_key::<UnitVariants>();
+    assert_map_key::<EnumOverride>();
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<EnumKeyed>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "hash_map": { "values": { "type": "uint32" } },
+                "btree_map": { "values": { "type": "string" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
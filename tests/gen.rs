@@ -1,4 +1,5 @@
-use jtd_derive::{GenError, Generator, JsonTypedef};
+use jtd_derive::schema::{DiscriminatorError, Schema, SchemaType, TypeSchema, ValidationError};
+use jtd_derive::{BigInts, DefinitionOrder, GenError, Generator, JsonTypedef, RefStyle};
 
 #[derive(JsonTypedef)]
 #[allow(dead_code)]
@@ -27,9 +28,735 @@ fn name_collisions() {
         .naming_short()
         .build()
         .into_root_schema::<Wrapping>()
-        .unwrap_err();
+        .unwrap_err()
+    else {
+        panic!("expected a name collision");
+    };
 
     assert_eq!(id, "Foo");
     assert!([type1.as_str(), type2.as_str()].contains(&"gen::Foo"));
     assert!([type1.as_str(), type2.as_str()].contains(&"gen::foo::Foo"));
 }
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Plain {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(deny_unknown_fields)]
+#[allow(dead_code)]
+struct Denying {
+    x: u32,
+}
+
+#[test]
+fn default_additional_properties() {
+    let gen_schema = |allow| {
+        serde_json::to_value(
+            Generator::builder()
+                .default_additional_properties(allow)
+                .build()
+                .into_root_schema::<Plain>()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        gen_schema(true),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+    assert_eq!(
+        gen_schema(false),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+            },
+        }}
+    );
+
+    // the attribute can't be overridden by the generator
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .default_additional_properties(true)
+                .build()
+                .into_root_schema::<Denying>()
+                .unwrap(),
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+            },
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Outer {
+    plain: Plain,
+    denying: Denying,
+}
+
+#[test]
+fn definition_key_transform() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .top_level_ref()
+                .definition_key_transform(|key| format!("api.{}", key.replace("::", ".")))
+                .build()
+                .into_root_schema::<Outer>()
+                .unwrap(),
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "api.gen.Outer": {
+                    "properties": {
+                        "plain": { "ref": "api.gen.Plain" },
+                        "denying": { "ref": "api.gen.Denying" },
+                    },
+                    "additionalProperties": true,
+                },
+                "api.gen.Plain": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+                "api.gen.Denying": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                },
+            },
+            "ref": "api.gen.Outer",
+        }}
+    );
+}
+
+#[test]
+fn definition_key_transform_collision() {
+    let GenError::NameCollision { type1, type2, id } = Generator::builder()
+        .definition_key_transform(|_| "same".to_string())
+        .build()
+        .into_root_schema::<Outer>()
+        .unwrap_err()
+    else {
+        panic!("expected a name collision");
+    };
+
+    assert_eq!(id, "same");
+    assert_eq!(
+        [type1, type2]
+            .into_iter()
+            .collect::<std::collections::BTreeSet<_>>(),
+        ["gen::Denying".to_string(), "gen::Plain".to_string()].into()
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Ints {
+    small: u8,
+    signed: i16,
+    #[typedef(metadata(maximum = "100"))]
+    limited: u8,
+    float: f32,
+}
+
+#[test]
+fn int_ranges() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .emit_int_ranges()
+                .build()
+                .into_root_schema::<Ints>()
+                .unwrap(),
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "small": {
+                    "type": "uint8",
+                    "metadata": { "minimum": 0, "maximum": 255 },
+                },
+                "signed": {
+                    "type": "int16",
+                    "metadata": { "minimum": -32768, "maximum": 32767 },
+                },
+                "limited": {
+                    "type": "uint8",
+                    "metadata": { "minimum": 0, "maximum": 100 },
+                },
+                "float": { "type": "float32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Nullables {
+    number: Option<u32>,
+    foo: Option<Foo>,
+    required: u32,
+}
+
+#[test]
+fn nullable_metadata() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .emit_nullable_metadata()
+                .build()
+                .into_root_schema::<Nullables>()
+                .unwrap(),
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "gen::Foo": { "enum": ["Bar"] },
+            },
+            "properties": {
+                "number": {
+                    "type": "uint32",
+                    "nullable": true,
+                    "metadata": { "nullable": true },
+                },
+                "foo": {
+                    "ref": "gen::Foo",
+                    "nullable": true,
+                    "metadata": { "nullable": true },
+                },
+                "required": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[test]
+fn schema_hash() {
+    let gen_schema = || {
+        serde_json::to_value(
+            Generator::builder()
+                .emit_hash()
+                .build()
+                .into_root_schema::<Outer>()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let schema = gen_schema();
+    let hash = schema["metadata"]["schemaHash"].as_str().unwrap();
+
+    assert_eq!(hash.len(), 16);
+    assert_eq!(schema, gen_schema());
+
+    let other = serde_json::to_value(
+        Generator::builder()
+            .emit_hash()
+            .build()
+            .into_root_schema::<Plain>()
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_ne!(other["metadata"]["schemaHash"], hash);
+}
+
+#[test]
+fn ref_style() {
+    let gen_schema = |style| {
+        serde_json::to_value(
+            Generator::builder()
+                .naming_short()
+                .ref_style(style)
+                .build()
+                .into_root_schema::<Outer>()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let definitions = serde_json::json! {{
+        "Plain": {
+            "properties": {
+                "x": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        },
+        "Denying": {
+            "properties": {
+                "x": { "type": "uint32" },
+            },
+        },
+    }};
+
+    assert_eq!(
+        gen_schema(RefStyle::Name),
+        serde_json::json! {{
+            "definitions": definitions,
+            "properties": {
+                "plain": { "ref": "Plain" },
+                "denying": { "ref": "Denying" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+    assert_eq!(
+        gen_schema(RefStyle::Pointer),
+        serde_json::json! {{
+            "definitions": definitions,
+            "properties": {
+                "plain": { "ref": "#/definitions/Plain" },
+                "denying": { "ref": "#/definitions/Denying" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(untagged)]
+#[allow(dead_code)]
+enum IdOrName {
+    Id(u32),
+    Name(String),
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Lossy {
+    size: u64,
+    modified: std::time::SystemTime,
+    tags: std::collections::HashSet<String>,
+    more_tags: std::collections::HashSet<String>,
+    initial: char,
+    owner: IdOrName,
+}
+
+#[test]
+fn warnings() {
+    let (_, warnings) = Generator::builder()
+        .big_ints(BigInts::Float64)
+        .collect_warnings()
+        .build()
+        .into_root_schema_with_warnings::<Lossy>()
+        .unwrap();
+
+    // only the messages are matched, not how the types are named; the second
+    // one is for the seconds of the `SystemTime`
+    assert_eq!(warnings.len(), 5);
+    for (warning, message) in warnings.iter().zip([
+        "described as `float64`, which only holds integers up to 2^53 exactly",
+        "described as `float64`, which only holds integers up to 2^53 exactly",
+        "set elements are unique, but the schema allows duplicates",
+        "a `char` is serialized as a single character, but the schema allows any string",
+        "untagged enums have the empty schema, which accepts any value",
+    ]) {
+        assert!(warning.ends_with(message), "{}", warning);
+    }
+
+    let (_, warnings) = Generator::builder()
+        .big_ints(BigInts::Float64)
+        .build()
+        .into_root_schema_with_warnings::<Lossy>()
+        .unwrap();
+
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn big_ints() {
+    let GenError::Rejected { type_name, .. } = Generator::default()
+        .into_root_schema::<Lossy>()
+        .unwrap_err()
+    else {
+        panic!("expected a rejection");
+    };
+    assert_eq!(type_name, "u64");
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .big_ints(BigInts::Float64)
+                .build()
+                .into_root_schema::<Vec<i128>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{ "elements": { "type": "float64" } }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Span {
+    start: u32,
+    end: u32,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Spans {
+    first: Span,
+    last: Option<Span>,
+    all: Vec<Span>,
+}
+
+#[test]
+fn dedup_anonymous() {
+    let gen_schema = |threshold| {
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .dedup_anonymous(threshold)
+                .build()
+                .into_root_schema::<Spans>()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        gen_schema(2),
+        serde_json::json! {{
+            "definitions": {
+                "anonymous1": {
+                    "properties": {
+                        "start": { "type": "uint32" },
+                        "end": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "properties": {
+                "first": { "ref": "anonymous1" },
+                "last": { "ref": "anonymous1", "nullable": true },
+                "all": { "elements": { "ref": "anonymous1" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+
+    // three occurrences don't exceed a threshold of 3
+    assert!(gen_schema(3).get("definitions").is_none());
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum Edit {
+    Insert { at: Span, len: u32 },
+    Delete { at: Span, len: u32 },
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Edits {
+    first: Edit,
+    last: Edit,
+}
+
+#[test]
+fn dedup_anonymous_keeps_mapping_inline() {
+    let gen = || {
+        Generator::builder()
+            .prefer_inline()
+            .dedup_anonymous(1)
+            .strict()
+            .build()
+    };
+
+    assert_eq!(
+        serde_json::to_value(gen().into_root_schema::<Edit>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "anonymous1": {
+                    "properties": {
+                        "start": { "type": "uint32" },
+                        "end": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "discriminator": "type",
+            "mapping": {
+                "Insert": {
+                    "properties": {
+                        "at": { "ref": "anonymous1" },
+                        "len": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Delete": {
+                    "properties": {
+                        "at": { "ref": "anonymous1" },
+                        "len": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+        }}
+    );
+
+    // the discriminator itself can still be hoisted as a whole
+    let root = gen().into_root_schema::<Edits>().unwrap();
+    assert!(matches!(
+        root.definitions["anonymous1"].ty,
+        SchemaType::Discriminator { .. }
+    ));
+}
+
+mod graph {
+    use jtd_derive::JsonTypedef;
+
+    #[derive(JsonTypedef)]
+    #[allow(dead_code)]
+    pub struct Top {
+        b: B,
+        a: A,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(dead_code)]
+    pub struct A {
+        c: C,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(dead_code)]
+    pub struct B {
+        c: C,
+        a: A,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(dead_code)]
+    pub struct C {
+        x: u32,
+        cycle: Option<Box<D>>,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(dead_code)]
+    pub struct D {
+        cycle: Option<Box<C>>,
+    }
+}
+
+#[test]
+fn definition_order() {
+    let order = |order| {
+        let ordered = Generator::builder()
+            .naming_short()
+            .top_level_ref()
+            .definition_order(order)
+            .build()
+            .into_ordered_root_schema::<graph::Top>()
+            .unwrap();
+        let json = serde_json::to_string(&ordered).unwrap();
+
+        let mut keys = ["Top", "A", "B", "C", "D"];
+        keys.sort_by_key(|key| json.find(&format!("\"{}\":{{", key)).unwrap());
+        assert_eq!(ordered.definition_order().collect::<Vec<_>>(), keys);
+        let canonical = ordered.to_pretty_canonical_string();
+        let mut canonical_keys = keys;
+        canonical_keys.sort_by_key(|key| canonical.find(&format!("\"{}\": {{", key)).unwrap());
+        assert_eq!(canonical_keys, keys);
+        // the root schema itself doesn't depend on the order
+        assert_eq!(
+            ordered.root(),
+            &Generator::builder()
+                .naming_short()
+                .top_level_ref()
+                .build()
+                .into_root_schema::<graph::Top>()
+                .unwrap()
+        );
+        keys
+    };
+
+    assert_eq!(
+        order(DefinitionOrder::Alphabetical),
+        ["A", "B", "C", "D", "Top"]
+    );
+    assert_eq!(
+        order(DefinitionOrder::Insertion),
+        ["Top", "B", "C", "D", "A"]
+    );
+    // `C` and `D` refer to each other, so one of them has to come first
+    assert_eq!(
+        order(DefinitionOrder::Topological),
+        ["D", "C", "A", "B", "Top"]
+    );
+}
+
+fn malformed_discriminator(_: &mut Generator) -> Schema {
+    Schema {
+        ty: SchemaType::Discriminator {
+            discriminator: "type",
+            mapping: [(
+                "Foo",
+                Schema {
+                    ty: SchemaType::Type {
+                        r#type: TypeSchema::String,
+                    },
+                    ..Schema::default()
+                },
+            )]
+            .into(),
+        },
+        ..Schema::default()
+    }
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Malformed {
+    #[typedef(schema_with = "malformed_discriminator")]
+    x: String,
+}
+
+#[test]
+fn strict() {
+    // the default generator doesn't check anything
+    assert!(Generator::default().into_root_schema::<Malformed>().is_ok());
+
+    assert_eq!(
+        Generator::strict()
+            .into_root_schema::<Malformed>()
+            .unwrap_err(),
+        GenError::InvalidSchema(ValidationError::InvalidVariant {
+            path: "/properties/x".to_string(),
+            source: DiscriminatorError::NotProperties { variant: "Foo" },
+        })
+    );
+
+    assert!(Generator::strict().into_root_schema::<graph::Top>().is_ok());
+}
+
+fn require_description(schema: &Schema) -> Result<(), String> {
+    match schema.metadata.get("description") {
+        Some(_) => Ok(()),
+        None => Err("missing a description".to_string()),
+    }
+}
+
+#[derive(JsonTypedef)]
+#[typedef(validate_with = "require_description")]
+#[typedef(metadata(description = "\"Described.\""))]
+#[allow(dead_code)]
+struct Described {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(validate_with = "require_description")]
+#[allow(dead_code)]
+struct Undescribed {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Both {
+    described: Described,
+    undescribed: Undescribed,
+}
+
+#[test]
+fn validate_with() {
+    assert!(Generator::default().into_root_schema::<Described>().is_ok());
+
+    assert_eq!(
+        Generator::default().into_root_schema::<Both>().unwrap_err(),
+        GenError::Rejected {
+            type_name: "gen::Undescribed".to_string(),
+            message: "missing a description".to_string(),
+        }
+    );
+}
+
+#[test]
+fn metrics() {
+    let gen_schema = |gen: Generator| gen.into_root_schema::<Spans>().unwrap();
+
+    let inlined = gen_schema(Generator::builder().prefer_inline().build());
+    // the top level, 3 properties, 3 nested properties schemas with 2 properties
+    // each and the elements schema
+    assert_eq!(inlined.count_nodes(), 1 + 3 + 3 * 2 + 1);
+
+    let deduped = gen_schema(
+        Generator::builder()
+            .prefer_inline()
+            .dedup_anonymous(2)
+            .build(),
+    );
+    // the top level with 3 refs and an elements schema, plus the definition
+    assert_eq!(deduped.count_nodes(), 1 + 3 + 1 + 3);
+    assert!(deduped.serialized_size() < inlined.serialized_size());
+    assert_eq!(
+        deduped.serialized_size(),
+        serde_json::to_string(&deduped).unwrap().len()
+    );
+}
+
+#[test]
+fn definitions_count() {
+    struct Counted;
+
+    impl JsonTypedef for Counted {
+        fn schema(gen: &mut Generator) -> Schema {
+            assert_eq!(gen.definitions_count(), 0);
+            let schema = gen.sub_schema::<Spans>();
+            // `Spans` and `Span`
+            assert_eq!(gen.definitions_count(), 2);
+            schema
+        }
+
+        fn referenceable() -> bool {
+            false
+        }
+
+        fn names() -> jtd_derive::Names {
+            jtd_derive::Names::anonymous()
+        }
+    }
+
+    assert_eq!(
+        Generator::default()
+            .into_root_schema::<Counted>()
+            .unwrap()
+            .definitions
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn schema_default() {
+    assert_eq!(
+        Spans::schema_default(),
+        Generator::default().into_root_schema::<Spans>()
+    );
+    assert_eq!(
+        <Option<Vec<Span>>>::schema_default(),
+        Generator::default().into_root_schema::<Option<Vec<Span>>>()
+    );
+}
@@ -0,0 +1,63 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::num::NonZeroU32;
+
+use jtd_derive::{assert_schema_matches, JsonTypedef};
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct AllTypes {
+    bool: bool,
+    u8: u8,
+    u16: u16,
+    u32: u32,
+    i8: i8,
+    i16: i16,
+    i32: i32,
+    f32: f32,
+    f64: f64,
+    char: char,
+    string: String,
+    str: &'static str,
+    non_zero: NonZeroU32,
+    ip: std::net::IpAddr,
+    option: Option<u32>,
+    vec: Vec<String>,
+    vec_deque: VecDeque<u8>,
+    array: [i16; 3],
+    hash_set: HashSet<String>,
+    btree_set: BTreeSet<u32>,
+    hash_map: HashMap<String, f64>,
+    btree_map: BTreeMap<String, Vec<bool>>,
+    boxed: Box<Nested>,
+    json: serde_json::Value,
+    nested: Nested,
+    unit_enum: UnitEnum,
+    tagged_enum: TaggedEnum,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Nested {
+    x: u32,
+    y: Option<String>,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+enum UnitEnum {
+    Foo,
+    Bar,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum TaggedEnum {
+    Foo { nested: Nested },
+    Bar { unit: UnitEnum },
+}
+
+#[test]
+fn all_types() {
+    assert_schema_matches!(AllTypes, "tests/golden/all_types.json");
+}
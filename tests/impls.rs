@@ -0,0 +1,262 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, LinkedList, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use jtd_derive::{Generator, JsonTypedef, Names};
+
+#[test]
+fn map_of_optional_values() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<HashMap<String, Option<u32>>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "values": {
+                "type": "uint32",
+                "nullable": true,
+            },
+        }}
+    );
+}
+
+#[test]
+fn json_value() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Vec<serde_json::Value>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "elements": {},
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Config {
+    counter: Mutex<u32>,
+    name: RefCell<String>,
+    shared: Arc<Mutex<u32>>,
+}
+
+#[test]
+fn interior_mutability() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Config>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "counter": { "type": "uint32" },
+                "name": { "type": "string" },
+                "shared": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Unsized {
+    boxed: Box<str>,
+    shared: std::rc::Rc<str>,
+    slice: Arc<[u32]>,
+}
+
+#[test]
+fn unsized_smart_pointers() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Unsized>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "boxed": { "type": "string" },
+                "shared": { "type": "string" },
+                "slice": { "elements": { "type": "uint32" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[test]
+fn vec_deque() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<VecDeque<String>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "elements": { "type": "string" },
+        }}
+    );
+}
+
+#[test]
+fn linked_list() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<LinkedList<Option<u8>>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "elements": { "type": "uint8", "nullable": true },
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Paths<'a> {
+    owned: std::path::PathBuf,
+    borrowed: &'a std::path::Path,
+}
+
+#[test]
+fn paths() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Paths>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "std::path::Path": {
+                    "metadata": {
+                        "description": "A file system path. Serializing a path that isn't \
+                            valid Unicode fails, which depends on the platform it was created on.",
+                    },
+                    "type": "string",
+                },
+            },
+            "properties": {
+                "owned": { "ref": "std::path::Path" },
+                "borrowed": { "ref": "std::path::Path" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[allow(dead_code)]
+struct Grid<T, const N: usize>([[T; N]; N]);
+
+impl<T: JsonTypedef, const N: usize> JsonTypedef for Grid<T, N> {
+    fn schema(gen: &mut jtd_derive::Generator) -> jtd_derive::schema::Schema {
+        Vec::<Vec<T>>::schema(gen)
+    }
+
+    fn referenceable() -> bool {
+        true
+    }
+
+    fn names() -> Names {
+        Names::new("Grid", "impls::Grid")
+            .with_type_params([T::names()])
+            .with_const_params([N])
+    }
+}
+
+#[test]
+fn manual_impl_names() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .naming_short()
+                .build()
+                .into_root_schema::<Vec<Grid<u8, 3>>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "Grid<uint8, 3>": {
+                    "elements": { "elements": { "type": "uint8" } },
+                },
+            },
+            "elements": { "ref": "Grid<uint8, 3>" },
+        }}
+    );
+}
+
+#[test]
+fn json_number() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<serde_json::Number>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "type": "float64",
+        }}
+    );
+}
+
+#[test]
+fn system_time() {
+    let time = std::time::UNIX_EPOCH + std::time::Duration::new(1_700_000_000, 5);
+    assert_eq!(
+        serde_json::to_value(time).unwrap(),
+        serde_json::json! {{ "secs_since_epoch": 1_700_000_000, "nanos_since_epoch": 5 }}
+    );
+
+    // the seconds are a `u64`
+    assert!(matches!(
+        Generator::default().into_root_schema::<std::time::SystemTime>(),
+        Err(jtd_derive::GenError::Rejected { .. })
+    ));
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .big_ints(jtd_derive::BigInts::Float64)
+                .build()
+                .into_root_schema::<Vec<std::time::SystemTime>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "std::time::SystemTime": {
+                    "properties": {
+                        "secs_since_epoch": { "type": "float64" },
+                        "nanos_since_epoch": { "type": "uint32" },
+                    },
+                },
+            },
+            "elements": { "ref": "std::time::SystemTime" },
+        }}
+    );
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+    #[derive(JsonTypedef)]
+    #[allow(dead_code)]
+    struct Payload {
+        raw: bytes::Bytes,
+        buf: bytes::BytesMut,
+        #[typedef(type = "String")]
+        base64: bytes::Bytes,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Payload>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "raw": { "elements": { "type": "uint8" } },
+                "buf": { "elements": { "type": "uint8" } },
+                "base64": { "type": "string" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
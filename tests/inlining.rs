@@ -1,4 +1,4 @@
-use jtd_derive::{Generator, JsonTypedef};
+use jtd_derive::{GenError, Generator, JsonTypedef};
 
 #[derive(JsonTypedef)]
 #[allow(unused)]
@@ -92,3 +92,69 @@ fn prefer_ref() {
         }}
     );
 }
+
+#[test]
+fn inline_only() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .inline_only()
+                .build()
+                .into_root_schema::<Bar>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "bar": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+
+    assert_eq!(
+        Generator::builder()
+            .inline_only()
+            .build()
+            .into_root_schema::<Foo>()
+            .unwrap_err(),
+        GenError::RefRequired {
+            type_name: "inlining::Recursive".to_string()
+        }
+    );
+}
+
+#[test]
+fn canonicalize() {
+    let inlined = Generator::builder()
+        .prefer_inline()
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    let by_ref = Generator::builder()
+        .top_level_ref()
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+
+    assert_ne!(inlined.schema, by_ref.schema);
+    assert_eq!(
+        inlined.schema.canonicalize(&inlined.definitions),
+        by_ref.schema.canonicalize(&by_ref.definitions)
+    );
+
+    // the recursive type stays a ref
+    assert_eq!(
+        serde_json::to_value(by_ref.schema.canonicalize(&by_ref.definitions)).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "bar": {
+                    "properties": { "bar": { "type": "uint32" } },
+                    "additionalProperties": true,
+                },
+                "recursive": { "ref": "inlining::Recursive" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
@@ -139,3 +139,240 @@ fn overwriting() {
         }}
     );
 }
+
+#[test]
+fn computed() {
+    fn meta() -> serde_json::Map<String, serde_json::Value> {
+        let mut map = serde_json::Map::new();
+        map.insert("x".to_string(), "computed".into());
+        map.insert(format!("{}_{}", "y", 5), 5.into());
+        map
+    }
+
+    #[derive(JsonTypedef)]
+    #[typedef(metadata(x = "\"stuff\"", z = "3"), metadata_from = "meta")]
+    #[allow(unused)]
+    struct Foo {
+        bar: u32,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "bar": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+            "metadata": {
+                "x": "computed",
+                "y_5": 5,
+                "z": 3,
+            }
+        }}
+    );
+}
+
+#[test]
+fn field_group() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        name: String,
+        #[typedef(group = "advanced")]
+        retries: u32,
+        #[typedef(group = "advanced", metadata(description = "\"in seconds\""))]
+        timeout: u32,
+        #[typedef(group = "\"quoted\" \\ tab\t \u{7f} 🦀")]
+        odd: u32,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "name": { "type": "string" },
+                "retries": {
+                    "type": "uint32",
+                    "metadata": { "group": "advanced" },
+                },
+                "timeout": {
+                    "type": "uint32",
+                    "metadata": { "group": "advanced", "description": "in seconds" },
+                },
+                "odd": {
+                    "type": "uint32",
+                    "metadata": { "group": "\"quoted\" \\ tab\t \u{7f} 🦀" },
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[test]
+fn variant_docs() {
+    #[derive(JsonTypedef)]
+    #[typedef(tag = "type")]
+    #[allow(unused)]
+    enum Shape {
+        /// A circle.
+        Circle {
+            radius: f32,
+        },
+        /// A rectangle.
+        ///
+        /// Squares are rectangles too.
+        Rectangle {
+            width: f32,
+            height: f32,
+        },
+        Point {
+            x: f32,
+        },
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Shape>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Circle": {
+                    "properties": { "radius": { "type": "float32" } },
+                    "additionalProperties": true,
+                    "metadata": { "description": "A circle." },
+                },
+                "Rectangle": {
+                    "properties": {
+                        "width": { "type": "float32" },
+                        "height": { "type": "float32" },
+                    },
+                    "additionalProperties": true,
+                    "metadata": { "description": "A rectangle.\n\nSquares are rectangles too." },
+                },
+                "Point": {
+                    "properties": { "x": { "type": "float32" } },
+                    "additionalProperties": true,
+                },
+            },
+        }}
+    );
+}
+
+#[test]
+fn jtd_codegen_descriptions() {
+    /// A pet.
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Pet {
+        /// What it answers to.
+        name: String,
+        species: Species,
+        #[typedef(metadata(description = "\"Overridden.\""))]
+        /// Not this.
+        age: u8,
+    }
+
+    /// Kinds of pets.
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    enum Species {
+        /// Woof.
+        Dog,
+        Cat,
+    }
+
+    let gen_schema = |dialect| {
+        serde_json::to_value(
+            Generator::builder()
+                .naming_short()
+                .ref_style(jtd_derive::RefStyle::Pointer)
+                .dialect(dialect)
+                .build()
+                .into_root_schema::<Pet>()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        gen_schema(jtd_derive::Dialect::JtdCodegen),
+        serde_json::json! {{
+            "definitions": {
+                "Species": {
+                    "enum": ["Dog", "Cat"],
+                    "metadata": {
+                        "description": "Kinds of pets.",
+                        "enumDescription": { "Dog": "Woof." },
+                    },
+                },
+            },
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "metadata": { "description": "What it answers to." },
+                },
+                // jtd-codegen only understands plain refs
+                "species": { "ref": "Species" },
+                "age": {
+                    "type": "uint8",
+                    "metadata": { "description": "Overridden." },
+                },
+            },
+            "additionalProperties": true,
+            "metadata": { "description": "A pet." },
+        }}
+    );
+
+    // doc comments are left out by default
+    assert_eq!(
+        gen_schema(jtd_derive::Dialect::Standard),
+        serde_json::json! {{
+            "definitions": {
+                "Species": {
+                    "enum": ["Dog", "Cat"],
+                },
+            },
+            "properties": {
+                "name": { "type": "string" },
+                "species": { "ref": "#/definitions/Species" },
+                "age": {
+                    "type": "uint8",
+                    "metadata": { "description": "Overridden." },
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[test]
+fn jtd_codegen_descriptions_with_enum_values() {
+    #[derive(JsonTypedef)]
+    #[typedef(rename_all = "lowercase", enum("cat", "bird", "dog"))]
+    #[allow(unused)]
+    enum Species {
+        /// Woof.
+        Dog,
+        /// Meow.
+        Cat,
+        /// Not one of the values.
+        Fish,
+    }
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .dialect(jtd_derive::Dialect::JtdCodegen)
+                .build()
+                .into_root_schema::<Species>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "enum": ["cat", "bird", "dog"],
+            "metadata": {
+                "enumDescription": { "dog": "Woof.", "cat": "Meow." },
+            },
+        }}
+    );
+}
@@ -31,3 +31,41 @@ fn recursive() {
         }}
     );
 }
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "op")]
+#[allow(unused)]
+enum Expr {
+    Num { value: i32 },
+    Add { left: Box<Expr>, right: Box<Expr> },
+}
+
+#[test]
+fn recursive_enum() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Expr>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "recursive::Expr": {
+                    "discriminator": "op",
+                    "mapping": {
+                        "Num": {
+                            "properties": {
+                                "value": { "type": "int32" },
+                            },
+                            "additionalProperties": true,
+                        },
+                        "Add": {
+                            "properties": {
+                                "left": { "ref": "recursive::Expr" },
+                                "right": { "ref": "recursive::Expr" },
+                            },
+                            "additionalProperties": true,
+                        },
+                    },
+                },
+            },
+            "ref": "recursive::Expr",
+        }}
+    );
+}
@@ -0,0 +1,175 @@
+use jtd_derive::{register_schema, GenError, Generator, JsonTypedef, SchemaRegistry};
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    bar: Bar,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Bar {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+enum Baz {
+    Qux,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+#[test]
+fn bundle() {
+    let mut registry = SchemaRegistry::new();
+    register_schema!(registry, Foo, Baz);
+
+    assert_eq!(
+        serde_json::to_value(registry.into_bundle(Generator::default()).unwrap()).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "registry::Foo": {
+                    "properties": {
+                        "bar": { "ref": "registry::Bar" },
+                    },
+                    "additionalProperties": true,
+                },
+                "registry::Baz": {
+                    "enum": ["Qux"],
+                },
+                "registry::Bar": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+        }}
+    );
+}
+
+#[test]
+fn bundle_referenced_types() {
+    // `Foo` refers to `Bar`, and `Tree` to itself
+    let mut registry = SchemaRegistry::new();
+    register_schema!(registry, Foo, Bar, Tree, Vec<Bar>);
+
+    assert_eq!(
+        serde_json::to_value(
+            registry
+                .into_bundle(Generator::builder().naming_short().build())
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "Foo": {
+                    "properties": {
+                        "bar": { "ref": "Bar" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Bar": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Tree": {
+                    "properties": {
+                        "children": { "elements": { "ref": "Tree" } },
+                    },
+                    "additionalProperties": true,
+                },
+                // can't be referenced, so it keeps the registered name
+                "Vec<Bar>": {
+                    "elements": { "ref": "Bar" },
+                },
+            },
+        }}
+    );
+}
+
+#[test]
+fn bundle_name_collision() {
+    let mut registry = SchemaRegistry::new();
+    register_schema!(registry, Foo);
+    registry.register("Bar", Baz::schema);
+
+    let GenError::NameCollision { type1, type2, id } = registry
+        .into_bundle(Generator::builder().naming_short().build())
+        .unwrap_err()
+    else {
+        panic!("expected a name collision");
+    };
+
+    assert_eq!(id, "Bar");
+    assert_eq!(type1, "Bar");
+    assert_eq!(type2, "registry::Bar");
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Point {
+    x: Bar,
+    y: Bar,
+}
+
+#[test]
+fn bundle_post_processing() {
+    let mut registry = SchemaRegistry::new();
+    register_schema!(registry, Point);
+
+    // the same settings as for a single type, e.g. deduplication, apply to bundles
+    assert_eq!(
+        serde_json::to_value(
+            registry
+                .into_bundle(
+                    Generator::builder()
+                        .naming_short()
+                        .prefer_inline()
+                        .dedup_anonymous(1)
+                        .build()
+                )
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "Point": {
+                    "properties": {
+                        "x": { "ref": "anonymous1" },
+                        "y": { "ref": "anonymous1" },
+                    },
+                    "additionalProperties": true,
+                },
+                "anonymous1": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+        }}
+    );
+
+    let mut registry = SchemaRegistry::new();
+    register_schema!(registry, Foo, Baz);
+    let ordered = registry
+        .into_ordered_bundle(
+            Generator::builder()
+                .naming_short()
+                .definition_order(jtd_derive::DefinitionOrder::Insertion)
+                .build(),
+        )
+        .unwrap();
+    assert_eq!(
+        ordered.definition_order().collect::<Vec<_>>(),
+        ["Baz", "Foo", "Bar"]
+    );
+}
@@ -0,0 +1,208 @@
+//! How `rename_all`, `rename`, `skip`, `optional` and `flatten` combine.
+//!
+//! The rules, matching `serde`:
+//!
+//! - `rename` wins over `rename_all`, and is used verbatim.
+//! - Skipped fields are gone before any renaming, so they can't collide with anything.
+//! - `rename_all` only applies to the fields of the type it's on. The keys merged
+//!   in by a flattened field are named by the flattened type itself.
+
+use jtd_derive::{Generator, JsonTypedef};
+use serde::Deserialize;
+
+fn schema<T: JsonTypedef>() -> serde_json::Value {
+    serde_json::to_value(Generator::default().into_root_schema::<T>().unwrap()).unwrap()
+}
+
+#[derive(JsonTypedef)]
+#[typedef(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct RenamedOptional {
+    plain_field: u32,
+    #[typedef(optional)]
+    optional_field: Option<u32>,
+    #[typedef(optional, rename = "OTHER_name")]
+    renamed_optional_field: Option<u32>,
+}
+
+#[test]
+fn renamed_and_optional() {
+    assert_eq!(
+        schema::<RenamedOptional>(),
+        serde_json::json! {{
+            "properties": {
+                "plainField": { "type": "uint32" },
+            },
+            "optionalProperties": {
+                "optionalField": { "type": "uint32" },
+                "OTHER_name": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(dead_code)]
+struct SkippedUnderRenameAll {
+    kept_field: u32,
+    #[typedef(skip)]
+    skipped_field: u32,
+    // would collide with `kept_field` if it weren't skipped
+    #[typedef(skip, rename = "KEPT_FIELD")]
+    shadow: String,
+}
+
+#[test]
+fn skipped_under_rename_all() {
+    assert_eq!(
+        schema::<SkippedUnderRenameAll>(),
+        serde_json::json! {{
+            "properties": {
+                "KEPT_FIELD": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct Child {
+    child_field: u32,
+    #[typedef(optional)]
+    optional_child_field: Option<u32>,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct RenamedChild {
+    other_child_field: u32,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(rename_all = "PascalCase")]
+#[allow(dead_code)]
+struct FlattenUnderRenameAll {
+    parent_field: u32,
+    #[typedef(flatten)]
+    child: Child,
+    #[typedef(flatten)]
+    renamed_child: Option<RenamedChild>,
+}
+
+#[test]
+fn flatten_under_rename_all() {
+    assert_eq!(
+        schema::<FlattenUnderRenameAll>(),
+        serde_json::json! {{
+            "properties": {
+                "ParentField": { "type": "uint32" },
+                "child_field": { "type": "uint32" },
+            },
+            "optionalProperties": {
+                "optional_child_field": { "type": "uint32" },
+                "otherChildField": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(dead_code)]
+struct SerdeMatrix {
+    first_field: u32,
+    #[serde(rename = "second")]
+    second_field: u32,
+    #[serde(skip)]
+    third_field: u32,
+    #[serde(default)]
+    #[typedef(optional)]
+    fourth_field: Option<u32>,
+    #[serde(flatten)]
+    child: Child,
+}
+
+#[test]
+fn serde_attrs() {
+    assert_eq!(
+        schema::<SerdeMatrix>(),
+        serde_json::json! {{
+            "properties": {
+                "first-field": { "type": "uint32" },
+                "second": { "type": "uint32" },
+                "child_field": { "type": "uint32" },
+            },
+            "optionalProperties": {
+                "fourth-field": { "type": "uint32" },
+                "optional_child_field": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+
+    // the schema describes what serde actually accepts
+    let parsed: SerdeMatrix = serde_json::from_value(serde_json::json! {{
+        "first-field": 1,
+        "second": 2,
+        "fourth-field": 4,
+        "child_field": 5,
+    }})
+    .unwrap();
+    assert_eq!(parsed.fourth_field, Some(4));
+    assert_eq!(parsed.child.child_field, 5);
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "kind", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum VariantMatrix {
+    #[typedef(rename_all = "camelCase")]
+    FirstVariant {
+        some_field: u32,
+        #[typedef(rename = "verbatim_name")]
+        renamed_field: u32,
+        #[typedef(skip)]
+        skipped_field: u32,
+        #[typedef(optional)]
+        optional_field: Option<u32>,
+        #[typedef(flatten)]
+        child: Child,
+    },
+    #[typedef(rename = "Second")]
+    SecondVariant { some_field: u32 },
+}
+
+#[test]
+fn variants() {
+    assert_eq!(
+        schema::<VariantMatrix>(),
+        serde_json::json! {{
+            "discriminator": "kind",
+            "mapping": {
+                "first_variant": {
+                    "properties": {
+                        "someField": { "type": "uint32" },
+                        "verbatim_name": { "type": "uint32" },
+                        "child_field": { "type": "uint32" },
+                    },
+                    "optionalProperties": {
+                        "optionalField": { "type": "uint32" },
+                        "optional_child_field": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Second": {
+                    "properties": {
+                        "some_field": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+        }}
+    );
+}
@@ -1,5 +1,6 @@
-use jtd_derive::{Generator, JsonTypedef};
-use serde::Deserialize;
+use jtd_derive::schema::{Schema, SchemaType, TypeSchema};
+use jtd_derive::{GenError, Generator, JsonTypedef};
+use serde::{Deserialize, Deserializer};
 
 #[derive(JsonTypedef, Deserialize)]
 #[serde(tag = "type")]
@@ -38,6 +39,79 @@ fn enum_respects_serde_tag_attr() {
     );
 }
 
+#[derive(JsonTypedef, Deserialize)]
+#[serde(tag = "type", content = "data")]
+#[allow(dead_code)]
+enum AdjacentStructVariants {
+    Bar { x: u32 },
+    Baz { y: String },
+}
+
+#[test]
+fn enum_respects_serde_tag_content_attrs() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<AdjacentStructVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Bar": {
+                    "properties": {
+                        "data": {
+                            "properties": {
+                                "x": {"type": "uint32"}
+                            },
+                            "additionalProperties": true
+                        }
+                    },
+                    "additionalProperties": true
+                },
+                "Baz": {
+                    "properties": {
+                        "data": {
+                            "properties": {
+                                "y": {"type": "string"}
+                            },
+                            "additionalProperties": true
+                        }
+                    },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(tag = "type", content = "data")]
+#[allow(dead_code)]
+enum AdjacentUnitVariants {
+    Bar,
+    Baz,
+}
+
+#[test]
+fn enum_unit_variants_respect_serde_tag_content_attrs() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<AdjacentUnitVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "type": {"enum": ["Bar", "Baz"]}
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
 #[derive(JsonTypedef, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[allow(dead_code)]
@@ -340,3 +414,296 @@ fn rename_all_enum_struct_variants() {
         }}
     );
 }
+
+fn u32_from_string<'de, D: Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+    String::deserialize(d)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+fn timestamp_schema(_: &mut Generator) -> Schema {
+    Schema {
+        ty: SchemaType::Type {
+            r#type: TypeSchema::Timestamp,
+        },
+        ..Schema::default()
+    }
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct CustomSerialization {
+    #[serde(deserialize_with = "u32_from_string")]
+    #[typedef(type = "String")]
+    x: u32,
+    #[serde(deserialize_with = "String::deserialize")]
+    #[typedef(schema_with = "timestamp_schema")]
+    y: String,
+}
+
+#[test]
+fn custom_serialization() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<CustomSerialization>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "string" },
+                "y": { "type": "timestamp" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum StringOrNumber {
+    String(String),
+    Number(u32),
+}
+
+#[test]
+fn untagged_enum() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<StringOrNumber>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "untagged": [
+                    { "type": "string" },
+                    { "type": "uint32" },
+                ],
+            },
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(tag = "type", content = "data")]
+#[typedef(single_variant_as_properties)]
+#[allow(dead_code)]
+enum AdjacentSingleVariant {
+    Bar { x: u32 },
+}
+
+#[test]
+fn adjacent_single_variant_as_properties() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<AdjacentSingleVariant>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "type": {"enum": ["Bar"]},
+                "data": {
+                    "properties": {
+                        "x": {"type": "uint32"}
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum SerdeRenames {
+    #[serde(rename = "bar.v1")]
+    Bar {
+        #[serde(rename = "x-coord")]
+        x: u32,
+        #[serde(rename(serialize = "ser", deserialize = "de"))]
+        y: u32,
+    },
+}
+
+#[test]
+fn serde_renames() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<SerdeRenames>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "bar.v1": {
+                    "properties": {
+                        "x-coord": {"type": "uint32"},
+                        "de": {"type": "uint32"},
+                    },
+                    "additionalProperties": true
+                },
+            }
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum VariantRenameAll {
+    #[serde(rename_all = "camelCase")]
+    Bar {
+        some_field: u32,
+    },
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    Baz {
+        some_field: u32,
+    },
+    Qux {
+        some_field: u32,
+    },
+}
+
+#[test]
+fn variant_rename_all() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<VariantRenameAll>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Bar": {
+                    "properties": {
+                        "someField": {"type": "uint32"},
+                    },
+                    "additionalProperties": true
+                },
+                "Baz": {
+                    "properties": {
+                        "SOME-FIELD": {"type": "uint32"},
+                    },
+                    "additionalProperties": true
+                },
+                "Qux": {
+                    "properties": {
+                        "some_field": {"type": "uint32"},
+                    },
+                    "additionalProperties": true
+                },
+            }
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct Inner {
+    x: u32,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct OtherInner {
+    y: String,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct SerdeFlatten {
+    #[serde(flatten)]
+    inner: Inner,
+    #[serde(flatten)]
+    maybe: Option<OtherInner>,
+}
+
+#[test]
+fn serde_flatten() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<SerdeFlatten>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": {"type": "uint32"},
+            },
+            "optionalProperties": {
+                "y": {"type": "string"},
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct SerdeFlattenTagged {
+    name: String,
+    #[serde(flatten)]
+    kind: StructVariants,
+}
+
+#[test]
+fn serde_flatten_discriminator() {
+    // serde can flatten an internally tagged enum, but its schema can't be
+    // merged into a properties schema
+    let err = Generator::default()
+        .into_root_schema::<SerdeFlattenTagged>()
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        GenError::Rejected { message, .. } if message
+            == "the flattened field `kind` must have a schema in the properties or values form"
+    ));
+}
+
+#[derive(Default)]
+struct NotTypedef;
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct SerdeSkip {
+    x: u32,
+    #[serde(skip)]
+    cache: NotTypedef,
+    #[serde(skip_serializing, skip_deserializing)]
+    other_cache: NotTypedef,
+    #[serde(skip_serializing)]
+    write_only: u32,
+}
+
+#[test]
+fn serde_skip() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<SerdeSkip>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": {"type": "uint32"},
+                "write_only": {"type": "uint32"},
+            },
+            "additionalProperties": true
+        }}
+    );
+}
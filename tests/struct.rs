@@ -84,3 +84,705 @@ fn nested() {
         }}
     );
 }
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Borrowed<'a> {
+    string: &'a str,
+    slice: &'a [u32],
+    named: &'a Cstruct,
+    mutable: &'a mut Cstruct,
+}
+
+#[test]
+fn borrowed_fields() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Borrowed>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "r#struct::Cstruct": {
+                    "properties": {
+                        "bar": { "type": "uint32" },
+                        "baz": { "type": "string", "nullable": true }
+                    },
+                    "additionalProperties": true
+                },
+            },
+            "properties": {
+                "string": { "type": "string" },
+                "slice": { "elements": { "type": "uint32" } },
+                "named": { "ref": "r#struct::Cstruct" },
+                "mutable": { "ref": "r#struct::Cstruct" },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Outermost(Middle);
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Middle(Innermost);
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Innermost {
+    x: u32,
+}
+
+#[test]
+fn chained_newtypes() {
+    // every layer is a distinct type, so it gets its own definition and refs
+    // point at the next layer rather than skipping to the innermost one
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Outermost>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "r#struct::Middle": {
+                    "ref": "r#struct::Innermost",
+                },
+                "r#struct::Innermost": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "ref": "r#struct::Middle",
+        }}
+    );
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .build()
+                .into_root_schema::<Outermost>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code, clippy::option_option)]
+struct Optionals {
+    #[typedef(optional)]
+    plain: Option<u32>,
+    #[typedef(optional)]
+    boxed: Box<Option<u32>>,
+    #[typedef(optional)]
+    double: Option<Option<u32>>,
+    #[typedef(optional)]
+    double_boxed: Option<Box<Option<u32>>>,
+    #[typedef(optional)]
+    shared: std::sync::Arc<Option<std::rc::Rc<Option<u32>>>>,
+    required: Option<u32>,
+    required_double: Option<Box<Option<u32>>>,
+}
+
+#[test]
+fn optional_fields() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Optionals>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "required": { "type": "uint32", "nullable": true },
+                "required_double": { "type": "uint32", "nullable": true },
+            },
+            "optionalProperties": {
+                "plain": { "type": "uint32" },
+                "boxed": { "type": "uint32" },
+                "double": { "type": "uint32", "nullable": true },
+                "double_boxed": { "type": "uint32", "nullable": true },
+                "shared": { "type": "uint32", "nullable": true },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+type UserId = u32;
+type Ids = Vec<UserId>;
+type Lookup<V> = std::collections::BTreeMap<String, V>;
+type Generic = CstructWithGenerics<'static, u8, 1>;
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Aliases {
+    id: UserId,
+    ids: Ids,
+    lookup: Lookup<Ids>,
+    generic: Generic,
+}
+
+#[test]
+fn type_aliases() {
+    // aliases are resolved by the compiler, so they're indistinguishable from
+    // the types they stand for, including their names
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Aliases>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "r#struct::CstructWithGenerics<uint8, 1>": {
+                    "properties": {
+                        "bar": { "type": "string" },
+                        "baz": { "elements": { "type": "uint8" } }
+                    },
+                    "additionalProperties": true
+                },
+            },
+            "properties": {
+                "id": { "type": "uint32" },
+                "ids": { "elements": { "type": "uint32" } },
+                "lookup": { "values": { "elements": { "type": "uint32" } } },
+                "generic": { "ref": "r#struct::CstructWithGenerics<uint8, 1>" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Renamed {
+    #[typedef(rename = "weird key.name")]
+    weird_key: u32,
+    #[typedef(rename = "snake_case")]
+    snake_case: u32,
+    other_field: u32,
+}
+
+#[test]
+fn renamed_fields() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Renamed>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "weird key.name": { "type": "uint32" },
+                "snake_case": { "type": "uint32" },
+                "otherField": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Metadata {
+    author: String,
+    #[typedef(optional)]
+    tags: Option<Vec<String>>,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Flattened {
+    id: u32,
+    #[typedef(flatten)]
+    metadata: Metadata,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct FlattenedOptional {
+    id: u32,
+    #[typedef(flatten)]
+    metadata: Option<Metadata>,
+}
+
+#[test]
+fn flatten() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Flattened>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "id": { "type": "uint32" },
+                "author": { "type": "string" },
+            },
+            "optionalProperties": {
+                "tags": { "elements": { "type": "string" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct FlattenedExtras {
+    name: String,
+    #[typedef(flatten)]
+    extras: std::collections::HashMap<String, u32>,
+}
+
+#[test]
+fn flatten_map() {
+    // even if the generator denies additional properties by default
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .default_additional_properties(false)
+                .build()
+                .into_root_schema::<FlattenedExtras>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "name": { "type": "string" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[test]
+fn flatten_optional() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<FlattenedOptional>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "id": { "type": "uint32" },
+            },
+            "optionalProperties": {
+                "author": { "type": "string" },
+                "tags": { "elements": { "type": "string" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct OptionalElements(Vec<Option<u32>>);
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct OptionalValues(std::collections::HashMap<String, Option<Vec<Option<String>>>>);
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct OptionalNewtypes {
+    elements: Option<OptionalElements>,
+    values: OptionalValues,
+}
+
+#[test]
+fn newtypes_with_nested_options() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<OptionalElements>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "elements": { "type": "uint32", "nullable": true },
+        }}
+    );
+
+    // nullability is kept on the ref rather than pushed into the definition,
+    // which is shared with non-nullable uses
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<OptionalNewtypes>()
+                .unwrap()
+        )
+        .unwrap()["properties"],
+        serde_json::json! {{
+            "elements": { "ref": "r#struct::OptionalElements", "nullable": true },
+            "values": { "ref": "r#struct::OptionalValues" },
+        }}
+    );
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .build()
+                .into_root_schema::<OptionalNewtypes>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "elements": {
+                    "elements": { "type": "uint32", "nullable": true },
+                    "nullable": true,
+                },
+                "values": {
+                    "values": {
+                        "elements": { "type": "string", "nullable": true },
+                        "nullable": true,
+                    },
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct OptionalMap(Option<std::collections::HashMap<String, String>>);
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct OptionalVec(Option<Vec<u32>>);
+
+#[test]
+fn newtypes_of_optional_collections() {
+    // it's the collection that's nullable, not its items
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<OptionalMap>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "values": { "type": "string" },
+            "nullable": true,
+        }}
+    );
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<OptionalVec>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "elements": { "type": "uint32" },
+            "nullable": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Id<T> {
+    id: u32,
+    #[typedef(skip)]
+    marker: std::marker::PhantomData<T>,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct TypedIds {
+    newtype: Id<Newtype>,
+    cstruct: Id<Cstruct>,
+}
+
+#[test]
+fn type_param_only_used_in_names() {
+    // `T` doesn't affect the shape, but `Id<Newtype>` and `Id<Cstruct>` are still
+    // distinct types with distinct definitions
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .naming_short()
+                .build()
+                .into_root_schema::<TypedIds>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "Id<Newtype>": {
+                    "properties": {
+                        "id": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Id<Cstruct>": {
+                    "properties": {
+                        "id": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "properties": {
+                "newtype": { "ref": "Id<Newtype>" },
+                "cstruct": { "ref": "Id<Cstruct>" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[allow(dead_code)]
+struct SmallVec<T> {
+    inline: [Option<T>; 4],
+    spilled: Vec<T>,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(transparent_elements = "u32")]
+#[allow(dead_code)]
+struct InlineIds(SmallVec<u32>);
+
+#[test]
+fn transparent_elements() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<InlineIds>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "elements": { "type": "uint32" },
+        }}
+    );
+}
+
+#[test]
+fn names_without_generator() {
+    let names = <Id<Vec<Newtype>> as JsonTypedef>::names();
+
+    assert_eq!(names.short, "Id");
+    assert_eq!(names.long, "r#struct::Id");
+    assert!(!names.nullable);
+    assert_eq!(
+        names.type_params,
+        [jtd_derive::Names::new("array", "array")
+            .with_type_params([jtd_derive::Names::new("Newtype", "r#struct::Newtype")])]
+    );
+    assert!(names.const_params.is_empty());
+}
+
+trait Kind {
+    type Output;
+}
+
+impl Kind for u8 {
+    type Output = Vec<String>;
+}
+
+#[derive(JsonTypedef)]
+#[typedef(bound = "<T as Kind>::Output: JsonTypedef")]
+#[allow(dead_code)]
+struct Associated<T: Kind> {
+    value: <T as Kind>::Output,
+}
+
+#[test]
+fn associated_type_with_bound() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Associated<u8>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "value": { "elements": { "type": "string" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct User {
+    name: String,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Response<T> {
+    data: T,
+    status: u16,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "result")]
+#[allow(dead_code)]
+enum Outcome<T> {
+    Ok { data: T },
+    Err { message: String },
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Endpoints {
+    get: Response<User>,
+    list: Response<Vec<User>>,
+    create: Outcome<User>,
+}
+
+#[test]
+fn generic_envelopes() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .naming_short()
+                .build()
+                .into_root_schema::<Endpoints>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "User": {
+                    "properties": {
+                        "name": { "type": "string" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Response<User>": {
+                    "properties": {
+                        "data": { "ref": "User" },
+                        "status": { "type": "uint16" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Response<array<User>>": {
+                    "properties": {
+                        "data": { "elements": { "ref": "User" } },
+                        "status": { "type": "uint16" },
+                    },
+                    "additionalProperties": true,
+                },
+                "Outcome<User>": {
+                    "discriminator": "result",
+                    "mapping": {
+                        "Ok": {
+                            "properties": {
+                                "data": { "ref": "User" },
+                            },
+                            "additionalProperties": true,
+                        },
+                        "Err": {
+                            "properties": {
+                                "message": { "type": "string" },
+                            },
+                            "additionalProperties": true,
+                        },
+                    },
+                },
+            },
+            "properties": {
+                "get": { "ref": "Response<User>" },
+                "list": { "ref": "Response<array<User>>" },
+                "create": { "ref": "Outcome<User>" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(as_values)]
+#[allow(dead_code)]
+struct Limits {
+    cpu: u32,
+    memory: u32,
+    #[typedef(optional)]
+    disk: Option<u32>,
+    #[typedef(skip)]
+    note: String,
+}
+
+#[test]
+fn as_values() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Limits>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "values": { "type": "uint32" },
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(map_key)]
+#[allow(dead_code)]
+struct Handle(String);
+
+// a key that doesn't derive anything
+#[allow(dead_code)]
+struct Email(String);
+
+impl jtd_derive::JsonTypedefMapKey for Email {}
+
+impl JsonTypedef for Email {
+    fn schema(gen: &mut Generator) -> jtd_derive::schema::Schema {
+        gen.sub_schema::<String>()
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> jtd_derive::Names {
+        String::names()
+    }
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Directory {
+    scores: std::collections::HashMap<Handle, u32>,
+    owners: std::collections::BTreeMap<Email, Handle>,
+}
+
+#[test]
+fn custom_map_keys() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .naming_short()
+                .build()
+                .into_root_schema::<Directory>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "Handle": { "type": "string" },
+            },
+            "properties": {
+                "scores": { "values": { "type": "uint32" } },
+                "owners": { "values": { "ref": "Handle" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}